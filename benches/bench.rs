@@ -0,0 +1,61 @@
+//! Micro-benchmarks for a few pure, publicly reachable operations.
+//!
+//! This deliberately doesn't cover packet parsing or row decoding as a
+//! whole: those live behind crate-private types (`SqlReadBytes`, the codec
+//! `Encode`/`Decode` traits) and exercising them meaningfully needs a
+//! connection to drive them over, which in turn needs an in-crate mock TDS
+//! server that doesn't exist yet. Once one lands, add benchmarks here that
+//! spin it up, run a `Client` against it, and measure end-to-end
+//! round-trips instead of just the isolated pieces reachable without a
+//! connection.
+//!
+//! The `nvarchar`/`nchar` UTF-16LE decode step is the one exception: it's a
+//! pure `&[u8] -> String` conversion with nothing connection-shaped about
+//! it, so it's reachable here through `tiberius::__bench_decode_utf16le`, a
+//! `#[doc(hidden)]` re-export that exists solely for this file.
+//!
+//! No benchmarking harness crate is used here on purpose, so this file has
+//! no extra dependencies to keep in sync with the rest of the crate; it's a
+//! plain binary run with `cargo bench`.
+
+use std::time::Instant;
+use tiberius::{ColumnData, Config, IntoSql};
+
+fn bench(name: &str, iterations: u32, mut f: impl FnMut()) {
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    let per_iter = elapsed / iterations.max(1);
+
+    println!("{name}: {per_iter:?}/iter ({iterations} iterations, {elapsed:?} total)");
+}
+
+fn main() {
+    bench("ado_net connection string parsing", 10_000, || {
+        let config = Config::from_ado_string(
+            "server=tcp:localhost,1433;user id=sa;password=abc123;TrustServerCertificate=true",
+        )
+        .unwrap();
+
+        std::hint::black_box(config);
+    });
+
+    bench("String -> ColumnData conversion", 100_000, || {
+        let data: ColumnData = "a fairly ordinary bit of text to convert".into_sql();
+        std::hint::black_box(data);
+    });
+
+    let utf16le: Vec<u8> = "a fairly ordinary bit of text to convert"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+
+    bench("nvarchar UTF-16LE -> String decode", 100_000, || {
+        let s = tiberius::__bench_decode_utf16le(&utf16le).unwrap();
+        std::hint::black_box(s);
+    });
+}
@@ -0,0 +1,168 @@
+//! Convenience wrappers around SQL Server Agent's `msdb` stored procedures
+//! for starting and monitoring jobs, so ops tooling built on this crate
+//! doesn't have to hand-write the `EXEC msdb.dbo.sp_*` calls and column
+//! layouts itself.
+//!
+//! These wrap `sp_start_job`, `sp_help_job` and `sp_help_jobhistory`, using
+//! the parameter and column names as documented by Microsoft. No live SQL
+//! Server Agent was available while writing this to check the decoded
+//! column types against a running instance, so treat the exact numeric
+//! types as a best effort.
+
+use crate::{Error, Row};
+
+/// A single row of [`sp_help_job`] output for one job: its current
+/// schedule/execution status and the outcome of its most recent run.
+///
+/// [`sp_help_job`]: https://learn.microsoft.com/en-us/sql/relational-databases/system-stored-procedures/sp-help-job-transact-sql
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentJobStatus {
+    name: String,
+    enabled: u8,
+    current_execution_status: i32,
+    last_run_outcome: i32,
+    last_run_date: i32,
+    last_run_time: i32,
+    next_run_date: i32,
+    next_run_time: i32,
+}
+
+impl AgentJobStatus {
+    /// The job's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the job is enabled. `0` means disabled.
+    pub fn enabled(&self) -> u8 {
+        self.enabled
+    }
+
+    /// The job's current execution status, e.g. `1` for executing, `4` for
+    /// idle. See [`sp_help_job`]'s documentation for the full list.
+    ///
+    /// [`sp_help_job`]: https://learn.microsoft.com/en-us/sql/relational-databases/system-stored-procedures/sp-help-job-transact-sql
+    pub fn current_execution_status(&self) -> i32 {
+        self.current_execution_status
+    }
+
+    /// The outcome of the job's most recent run: `0` failed, `1` succeeded,
+    /// `2` retry, `3` canceled, `5` in progress/unknown.
+    pub fn last_run_outcome(&self) -> i32 {
+        self.last_run_outcome
+    }
+
+    /// The date of the job's most recent run, as `YYYYMMDD`, or `0` if it
+    /// has never run.
+    pub fn last_run_date(&self) -> i32 {
+        self.last_run_date
+    }
+
+    /// The time of the job's most recent run, as `HHMMSS`.
+    pub fn last_run_time(&self) -> i32 {
+        self.last_run_time
+    }
+
+    /// The date the job is next scheduled to run, as `YYYYMMDD`, or `0` if
+    /// it has no schedule.
+    pub fn next_run_date(&self) -> i32 {
+        self.next_run_date
+    }
+
+    /// The time the job is next scheduled to run, as `HHMMSS`.
+    pub fn next_run_time(&self) -> i32 {
+        self.next_run_time
+    }
+}
+
+/// A single row of [`sp_help_jobhistory`] output: one step's outcome from
+/// one run of a job.
+///
+/// [`sp_help_jobhistory`]: https://learn.microsoft.com/en-us/sql/relational-databases/system-stored-procedures/sp-help-jobhistory-transact-sql
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentJobHistoryEntry {
+    instance_id: i32,
+    step_id: i32,
+    step_name: String,
+    run_status: i32,
+    message: String,
+    run_date: i32,
+    run_time: i32,
+    run_duration: i32,
+}
+
+impl AgentJobHistoryEntry {
+    /// The history entry's unique id.
+    pub fn instance_id(&self) -> i32 {
+        self.instance_id
+    }
+
+    /// The job step this entry reports on; `0` is the job outcome itself
+    /// rather than an individual step.
+    pub fn step_id(&self) -> i32 {
+        self.step_id
+    }
+
+    /// The name of the job step.
+    pub fn step_name(&self) -> &str {
+        &self.step_name
+    }
+
+    /// The outcome of this step: `0` failed, `1` succeeded, `2` retry, `3`
+    /// canceled, `4` in progress.
+    pub fn run_status(&self) -> i32 {
+        self.run_status
+    }
+
+    /// The message the step reported, e.g. the error text on failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The date the step ran, as `YYYYMMDD`.
+    pub fn run_date(&self) -> i32 {
+        self.run_date
+    }
+
+    /// The time the step ran, as `HHMMSS`.
+    pub fn run_time(&self) -> i32 {
+        self.run_time
+    }
+
+    /// How long the step ran, formatted as `HHMMSS` elapsed rather than a
+    /// duration since midnight.
+    pub fn run_duration(&self) -> i32 {
+        self.run_duration
+    }
+}
+
+fn required<'a, T: crate::FromSql<'a>>(row: &'a Row, column: &'static str) -> crate::Result<T> {
+    row.try_get(column)?
+        .ok_or_else(|| Error::Protocol(format!("msdb result missing column `{}`", column).into()))
+}
+
+pub(crate) fn parse_job_status(row: &Row) -> crate::Result<AgentJobStatus> {
+    Ok(AgentJobStatus {
+        name: required::<&str>(row, "name")?.to_owned(),
+        enabled: required(row, "enabled")?,
+        current_execution_status: required(row, "current_execution_status")?,
+        last_run_outcome: required(row, "last_run_outcome")?,
+        last_run_date: required(row, "last_run_date")?,
+        last_run_time: required(row, "last_run_time")?,
+        next_run_date: required(row, "next_run_date")?,
+        next_run_time: required(row, "next_run_time")?,
+    })
+}
+
+pub(crate) fn parse_job_history_entry(row: &Row) -> crate::Result<AgentJobHistoryEntry> {
+    Ok(AgentJobHistoryEntry {
+        instance_id: required(row, "instance_id")?,
+        step_id: required(row, "step_id")?,
+        step_name: required::<&str>(row, "step_name")?.to_owned(),
+        run_status: required(row, "run_status")?,
+        message: required::<&str>(row, "message")?.to_owned(),
+        run_date: required(row, "run_date")?,
+        run_time: required(row, "run_time")?,
+        run_duration: required(row, "run_duration")?,
+    })
+}
@@ -0,0 +1,277 @@
+//! Static SQL text inspection, for spotting ad-hoc queries that defeat SQL
+//! Server's plan cache.
+//!
+//! A statement sent with literal values baked into its text (`WHERE id = 42`)
+//! gets its own cached plan per distinct literal, instead of reusing the one
+//! plan a parameterized statement (`WHERE id = @P1`) would share across
+//! calls. This is a developer-time audit helper for finding those call
+//! sites; it doesn't touch a connection or change how [`Query`] sends a
+//! statement.
+//!
+//! [`Query`]: crate::Query
+
+/// A literal value [`parameterize`] found in a SQL string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Literal {
+    /// The literal's exact text as it appeared in the SQL, e.g. `"42"` or
+    /// `"'active'"` including the surrounding quotes.
+    pub text: String,
+    /// The byte offset of the literal's first character in the original SQL.
+    pub position: usize,
+    /// The kind of value the literal appears to hold.
+    pub kind: LiteralKind,
+}
+
+/// The surface-level kind of a [`Literal`], guessed from its lexical form
+/// alone - no attempt is made to resolve it against a schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralKind {
+    /// A single-quoted string, e.g. `'active'`.
+    String,
+    /// A run of digits with no decimal point, e.g. `42`.
+    Integer,
+    /// A run of digits containing a decimal point, e.g. `3.14`.
+    Float,
+}
+
+/// The result of scanning a SQL string with [`parameterize`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterizationReport {
+    /// The literals found, in the order they appear in the original SQL.
+    pub literals: Vec<Literal>,
+    /// `sql` with every found literal replaced by a `@PN` placeholder,
+    /// numbered in order of appearance starting from `1`, matching the
+    /// convention [`Query::new`] expects.
+    ///
+    /// [`Query::new`]: crate::Query::new
+    pub parameterized_sql: String,
+}
+
+impl ParameterizationReport {
+    /// Whether the scanned SQL already used no inline literals, i.e. is
+    /// already safe for plan cache reuse as far as this scan can tell.
+    pub fn is_parameterized(&self) -> bool {
+        self.literals.is_empty()
+    }
+}
+
+/// Scans `sql` for inline literal values and reports them, along with a
+/// version of `sql` with each one replaced by a `@PN` placeholder.
+///
+/// This is a lexical scan, not a parser: it recognizes single-quoted string
+/// literals (with `''` as an escaped quote) and bare numeric literals, while
+/// skipping over `--` line comments, `/* */` block comments, bracketed
+/// (`[...]`) and double-quoted identifiers so that punctuation inside them
+/// isn't misread as SQL. It doesn't understand expressions, so something
+/// like a numeric literal used only in a `TOP` clause or an `OFFSET` is
+/// flagged the same as one in a `WHERE` clause - the caller decides whether a
+/// given finding is actually worth parameterizing.
+///
+/// ```
+/// # use tiberius::analyze::parameterize;
+/// let report = parameterize("SELECT * FROM users WHERE status = 'active' AND age > 30");
+///
+/// assert_eq!(2, report.literals.len());
+/// assert_eq!(
+///     "SELECT * FROM users WHERE status = @P1 AND age > @P2",
+///     report.parameterized_sql,
+/// );
+/// ```
+pub fn parameterize(sql: &str) -> ParameterizationReport {
+    let mut literals = Vec::new();
+    let mut parameterized_sql = String::with_capacity(sql.len());
+
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        match b {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                let start = i;
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                parameterized_sql.push_str(&sql[start..i]);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let start = i;
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                i = (i + 2).min(bytes.len());
+                parameterized_sql.push_str(&sql[start..i]);
+            }
+            b'[' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b']' {
+                    i += 1;
+                }
+                i = (i + 1).min(bytes.len());
+                parameterized_sql.push_str(&sql[start..i]);
+            }
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == b'"' && bytes.get(i + 1) == Some(&b'"') {
+                        i += 2;
+                    } else if bytes[i] == b'"' {
+                        i += 1;
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+                parameterized_sql.push_str(&sql[start..i]);
+            }
+            b'\'' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == b'\'' && bytes.get(i + 1) == Some(&b'\'') {
+                        i += 2;
+                    } else if bytes[i] == b'\'' {
+                        i += 1;
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+
+                literals.push(Literal {
+                    text: sql[start..i].to_owned(),
+                    position: start,
+                    kind: LiteralKind::String,
+                });
+                parameterized_sql.push_str(&format!("@P{}", literals.len()));
+            }
+            b'0'..=b'9' => {
+                let start = i;
+                let mut is_float = false;
+
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    if bytes[i] == b'.' {
+                        is_float = true;
+                    }
+                    i += 1;
+                }
+
+                // A digit run glued onto the end of an identifier, e.g. the
+                // `1` in a `@P1` placeholder or a `col2` column name, is part
+                // of that identifier, not a literal.
+                let is_identifier_suffix = start > 0 && is_identifier_byte(bytes[start - 1]);
+
+                if is_identifier_suffix {
+                    parameterized_sql.push_str(&sql[start..i]);
+                } else {
+                    literals.push(Literal {
+                        text: sql[start..i].to_owned(),
+                        position: start,
+                        kind: if is_float {
+                            LiteralKind::Float
+                        } else {
+                            LiteralKind::Integer
+                        },
+                    });
+                    parameterized_sql.push_str(&format!("@P{}", literals.len()));
+                }
+            }
+            _ => {
+                let ch_len = utf8_char_len(b);
+                parameterized_sql.push_str(&sql[i..i + ch_len]);
+                i += ch_len;
+            }
+        }
+    }
+
+    ParameterizationReport {
+        literals,
+        parameterized_sql,
+    }
+}
+
+/// Whether `b` can appear inside a bare identifier or parameter name
+/// (`@P1`, `col2`).
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'@'
+}
+
+/// The byte length of the UTF-8 character starting with lead byte `b`.
+fn utf8_char_len(b: u8) -> usize {
+    if b & 0x80 == 0 {
+        1
+    } else if b & 0xE0 == 0xC0 {
+        2
+    } else if b & 0xF0 == 0xE0 {
+        3
+    } else {
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_string_and_numeric_literals() {
+        let report = parameterize("SELECT * FROM users WHERE status = 'active' AND age > 30");
+
+        assert_eq!(2, report.literals.len());
+        assert_eq!("'active'", report.literals[0].text);
+        assert_eq!(LiteralKind::String, report.literals[0].kind);
+        assert_eq!("30", report.literals[1].text);
+        assert_eq!(LiteralKind::Integer, report.literals[1].kind);
+        assert_eq!(
+            "SELECT * FROM users WHERE status = @P1 AND age > @P2",
+            report.parameterized_sql
+        );
+        assert!(!report.is_parameterized());
+    }
+
+    #[test]
+    fn already_parameterized_query_reports_no_literals() {
+        let report = parameterize("SELECT * FROM users WHERE id = @P1");
+
+        assert!(report.literals.is_empty());
+        assert!(report.is_parameterized());
+        assert_eq!(
+            "SELECT * FROM users WHERE id = @P1",
+            report.parameterized_sql
+        );
+    }
+
+    #[test]
+    fn recognizes_a_float_literal() {
+        let report = parameterize("SELECT * FROM prices WHERE amount = 19.99");
+
+        assert_eq!(1, report.literals.len());
+        assert_eq!(LiteralKind::Float, report.literals[0].kind);
+    }
+
+    #[test]
+    fn handles_escaped_quotes_inside_a_string_literal() {
+        let report = parameterize("SELECT * FROM notes WHERE body = 'it''s fine'");
+
+        assert_eq!(1, report.literals.len());
+        assert_eq!("'it''s fine'", report.literals[0].text);
+        assert_eq!(
+            "SELECT * FROM notes WHERE body = @P1",
+            report.parameterized_sql
+        );
+    }
+
+    #[test]
+    fn ignores_punctuation_inside_comments_and_identifiers() {
+        let sql =
+            "SELECT [my.col], \"other.col\" FROM t -- WHERE x = 1\n/* AND y = 2 */ WHERE z = 3";
+        let report = parameterize(sql);
+
+        assert_eq!(1, report.literals.len());
+        assert_eq!("3", report.literals[0].text);
+    }
+}
@@ -1,6 +1,11 @@
 mod auth;
 mod config;
 mod connection;
+mod diagnostics;
+mod negotiated;
+mod resolver;
+mod script;
+mod stats;
 
 mod tls;
 #[cfg(any(
@@ -13,21 +18,39 @@ mod tls_stream;
 pub use auth::*;
 pub use config::*;
 pub(crate) use connection::*;
+pub use diagnostics::SessionDiagnostics;
+pub use negotiated::NegotiatedSettings;
+pub use resolver::Resolver;
+pub use stats::ConnectionStats;
 
+use crate::error::{describe_param_types, truncate_sql_preview};
 use crate::tds::stream::ReceivedToken;
 use crate::{
+    agent,
+    impersonation::ImpersonationGuard,
     result::ExecuteResult,
+    retry::RetryStrategy,
+    schema, service_broker,
     tds::{
         codec::{self, IteratorJoin},
-        stream::{QueryStream, TokenStream},
+        stream::{QueryStream, RawQueryStream, TokenStream},
     },
-    BulkLoadRequest, ColumnFlag, SqlReadBytes, ToSql,
+    transaction::Transaction,
+    AgentJobHistoryEntry, AgentJobStatus, BulkLoadRequest, ColumnFlag, Error, Row, ServerKind,
+    ServiceBrokerMessage, SqlReadBytes, TableDescription, ToSql,
+};
+use codec::{
+    BatchRequest, ColumnData, PacketHeader, RpcParam, RpcProcId, TokenInfo, TokenRpcRequest,
+    TypeInfo, VarLenType,
 };
-use codec::{BatchRequest, ColumnData, PacketHeader, RpcParam, RpcProcId, TokenRpcRequest};
 use enumflags2::BitFlags;
 use futures::{AsyncRead, AsyncWrite};
 use futures_util::TryStreamExt;
-use std::{borrow::Cow, fmt::Debug};
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
 
 /// `Client` is the main entry point to the SQL Server, providing query
 /// execution capabilities.
@@ -70,9 +93,38 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
     ///
     /// [`Config`]: struct.Config.html
     pub async fn connect(config: Config, tcp_stream: S) -> crate::Result<Client<S>> {
-        Ok(Client {
+        let requested_database = config.database.clone();
+        let verify_database = config.verify_database;
+        let on_connect_sql = config.on_connect_sql.clone();
+
+        let mut client = Client {
             connection: Connection::connect(config, tcp_stream).await?,
-        })
+        };
+
+        if verify_database {
+            if let Some(requested) = requested_database {
+                let actual: Option<String> = client
+                    .query("SELECT DB_NAME() AS db_name", &[])
+                    .await?
+                    .into_row()
+                    .await?
+                    .and_then(|row| row.get::<&str, _>("db_name").map(|s| s.to_owned()));
+
+                let actual = actual.ok_or_else(|| {
+                    crate::Error::Protocol("SELECT DB_NAME() returned no row".into())
+                })?;
+
+                if actual != requested {
+                    return Err(crate::Error::DatabaseMismatch { requested, actual });
+                }
+            }
+        }
+
+        if let Some(sql) = on_connect_sql {
+            client.execute_batch(sql).await?;
+        }
+
+        Ok(client)
     }
 
     /// Executes SQL statements in the SQL Server, returning the number rows
@@ -124,13 +176,236 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
         params: &[&dyn ToSql],
     ) -> crate::Result<ExecuteResult> {
         self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+        let param_types = describe_param_types(params.iter().map(|p| p.to_sql().type_name()));
+
         let rpc_params = Self::rpc_params(query);
+        let rpc_query_params = params.iter().map(|s| (s.to_sql(), None));
 
-        let params = params.iter().map(|s| s.to_sql());
-        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, params)
-            .await?;
+        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, rpc_query_params)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
+
+        ExecuteResult::new(&mut self.connection)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))
+    }
+
+    /// Like [`execute`], but calling `on_info` with every informational
+    /// message the server sends back while the statement runs, in arrival
+    /// order. Useful for long-running procedures that report progress via
+    /// `RAISERROR('...', 0, 1) WITH NOWAIT`, which the server flushes to the
+    /// client immediately instead of holding until the batch finishes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::Config;
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// let result = client
+    ///     .execute_with_progress("EXEC LongRunningProcedure", &[], |info| {
+    ///         println!("progress: {}", info.message());
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn execute_with_progress<'a>(
+        &mut self,
+        query: impl Into<Cow<'a, str>>,
+        params: &[&dyn ToSql],
+        on_info: impl FnMut(&TokenInfo),
+    ) -> crate::Result<ExecuteResult> {
+        self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+        let param_types = describe_param_types(params.iter().map(|p| p.to_sql().type_name()));
+
+        let rpc_params = Self::rpc_params(query);
+        let rpc_query_params = params.iter().map(|s| (s.to_sql(), None));
+
+        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, rpc_query_params)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
+
+        ExecuteResult::new_with_progress(&mut self.connection, on_info)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))
+    }
+
+    /// Like [`execute`], but also returning any rows the statement sent
+    /// back, e.g. an `OUTPUT` clause on an `INSERT`/`UPDATE`/`DELETE`
+    /// returning the generated identity or the previous values of updated
+    /// columns. [`execute`] silently drops such rows; use this instead
+    /// whenever the statement contains an `OUTPUT` clause without `INTO`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::Config;
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// let (result, rows) = client
+    ///     .exec_returning(
+    ///         "INSERT INTO #Test (id) OUTPUT inserted.id VALUES (@P1)",
+    ///         &[&1i32],
+    ///     )
+    ///     .await?;
+    ///
+    /// assert_eq!(&[1], result.rows_affected());
+    /// assert_eq!(Some(1i32), rows[0].get(0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn exec_returning<'a>(
+        &mut self,
+        query: impl Into<Cow<'a, str>>,
+        params: &[&dyn ToSql],
+    ) -> crate::Result<(ExecuteResult, Vec<Row>)> {
+        self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+        let param_types = describe_param_types(params.iter().map(|p| p.to_sql().type_name()));
+
+        let rpc_params = Self::rpc_params(query);
+        let rpc_query_params = params.iter().map(|s| (s.to_sql(), None));
 
-        ExecuteResult::new(&mut self.connection).await
+        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, rpc_query_params)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
+
+        ExecuteResult::new_with_rows(&mut self.connection)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))
+    }
+
+    /// Like [`execute`], but automatically retries the statement while the
+    /// server keeps choosing it as a deadlock victim (SQL Server error
+    /// 1205), asking `strategy` how many attempts to make and how long to
+    /// wait between them. Any other error is returned immediately.
+    ///
+    /// The driver doesn't assume any particular async runtime, so the
+    /// caller supplies `sleep` to perform the actual wait, e.g.
+    /// `tokio::time::sleep` or `async_std::task::sleep`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, retry::JitteredBackoff};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = Config::from_ado_string("server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true")?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// let strategy = JitteredBackoff::new(Duration::from_millis(50), 2.0, 5);
+    ///
+    /// let results = client
+    ///     .execute_with_deadlock_retry(
+    ///         "UPDATE Accounts SET Balance = Balance - 10 WHERE Id = @P1",
+    ///         &[&1i32],
+    ///         &strategy,
+    ///         tokio::time::sleep,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn execute_with_deadlock_retry<'a, F, Fut>(
+        &mut self,
+        query: impl Into<Cow<'a, str>>,
+        params: &[&dyn ToSql],
+        strategy: &dyn RetryStrategy,
+        mut sleep: F,
+    ) -> crate::Result<ExecuteResult>
+    where
+        F: FnMut(std::time::Duration) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        let query = query.into();
+        let mut attempt = 0u32;
+
+        loop {
+            attempt += 1;
+
+            match self.execute(query.clone(), params).await {
+                Ok(result) => return Ok(result),
+                Err(e) if e.is_deadlock_victim() => match strategy.should_retry(&e, attempt) {
+                    Some(delay) => sleep(delay).await,
+                    None => return Err(e),
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Sends a minimal no-op statement (`SELECT 1`) and discards the result,
+    /// verifying the connection is still alive.
+    ///
+    /// Tiberius doesn't ship its own connection pool or a background
+    /// maintenance task (see the [crate-level docs] on pooling), so this
+    /// method is meant to be called from whatever periodic hook the pool
+    /// crate you're using provides, e.g. bb8's `ManageConnection::is_valid`
+    /// or deadpool's `Manager::recycle`. Calling it on an otherwise idle
+    /// connection also keeps NAT/firewall mappings from expiring the socket
+    /// out from under a pooled connection that's just sitting there waiting
+    /// for its next checkout.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::Config;
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// client.ping().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [crate-level docs]: index.html#other-features
+    pub async fn ping(&mut self) -> crate::Result<()> {
+        self.execute("SELECT 1", &[]).await?;
+        Ok(())
     }
 
     /// Executes SQL statements in the SQL Server, returning resulting rows.
@@ -186,19 +461,104 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
         'a: 'b,
     {
         self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+        let param_types = describe_param_types(params.iter().map(|p| p.to_sql().type_name()));
+
         let rpc_params = Self::rpc_params(query);
+        let rpc_query_params = params.iter().map(|p| (p.to_sql(), None));
 
-        let params = params.iter().map(|p| p.to_sql());
-        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, params)
-            .await?;
+        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, rpc_query_params)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
 
         let ts = TokenStream::new(&mut self.connection);
         let mut result = QueryStream::new(ts.try_unfold());
-        result.forward_to_metadata().await?;
+        result
+            .forward_to_metadata()
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))?;
 
         Ok(result)
     }
 
+    /// Like [`query`], but returns [`RawQueryStream`], whose rows carry their
+    /// exact `ROW`/`NBCROW` wire bytes instead of decoded values. Useful for
+    /// forwarding rows verbatim, or for skipping the cost of decoding columns
+    /// the caller doesn't need.
+    ///
+    /// [`query`]: #method.query
+    pub async fn raw_query<'a, 'b>(
+        &'a mut self,
+        query: impl Into<Cow<'b, str>>,
+        params: &'b [&'b dyn ToSql],
+    ) -> crate::Result<RawQueryStream<'a>>
+    where
+        'a: 'b,
+    {
+        self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+        let param_types = describe_param_types(params.iter().map(|p| p.to_sql().type_name()));
+
+        let rpc_params = Self::rpc_params(query);
+        let rpc_query_params = params.iter().map(|p| (p.to_sql(), None));
+
+        self.rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, rpc_query_params)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
+
+        let ts = TokenStream::new(&mut self.connection).with_raw_rows();
+        let mut result = RawQueryStream::new(ts.try_unfold());
+        result
+            .forward_to_metadata()
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))?;
+
+        Ok(result)
+    }
+
+    /// Runs a query and pushes each decoded row into `sender` as it arrives,
+    /// instead of collecting them into a [`QueryStream`]. Useful for
+    /// pipeline-style processing, where a consumer task or thread wants to
+    /// start working on rows before the whole result set has been read.
+    ///
+    /// The channel is closed (and this method returns) once the query
+    /// finishes or fails; a decode error is sent as an `Err` and ends the
+    /// stream early, matching how [`QueryStream`] surfaces errors.
+    ///
+    /// [`QueryStream`]: struct.QueryStream.html
+    pub async fn execute_streaming<'a, 'b>(
+        &'a mut self,
+        query: impl Into<Cow<'b, str>>,
+        params: &'b [&'b dyn ToSql],
+        mut sender: futures::channel::mpsc::UnboundedSender<crate::Result<Row>>,
+    ) -> crate::Result<()>
+    where
+        'a: 'b,
+    {
+        use futures_util::{SinkExt, StreamExt};
+
+        let mut rows = self.query(query, params).await?.into_row_stream();
+
+        while let Some(item) = rows.next().await {
+            let is_err = item.is_err();
+
+            if sender.send(item).await.is_err() {
+                // Receiver dropped; nothing left to deliver to.
+                break;
+            }
+
+            if is_err {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Execute multiple queries, delimited with `;` and return multiple result
     /// sets; one for each query.
     ///
@@ -238,19 +598,302 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
     {
         self.connection.flush_stream().await?;
 
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+
         let req = BatchRequest::new(query, self.connection.context().transaction_descriptor());
 
         let id = self.connection.context_mut().next_packet_id();
         self.connection.send(PacketHeader::batch(id), req).await?;
+        self.connection.record_statement();
 
         let ts = TokenStream::new(&mut self.connection);
 
         let mut result = QueryStream::new(ts.try_unfold());
-        result.forward_to_metadata().await?;
+        result
+            .forward_to_metadata()
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, "none".to_owned()))?;
 
         Ok(result)
     }
 
+    /// Like [`simple_query`], but for fire-and-forget SQL that doesn't
+    /// return rows, e.g. `SET` options, DDL, or a session setup script run
+    /// on every pooled checkout. Skips the `QueryStream`/metadata machinery
+    /// [`simple_query`] needs to support reading results back, draining only
+    /// the `DONE` and error tokens the server sends for the batch.
+    ///
+    /// # Warning
+    ///
+    /// Do not use this with any user specified input. Please resort to prepared
+    /// statements using the [`query`] method.
+    ///
+    /// [`simple_query`]: #method.simple_query
+    /// [`query`]: #method.query
+    pub async fn execute_batch<'a, 'b>(
+        &'a mut self,
+        query: impl Into<Cow<'b, str>>,
+    ) -> crate::Result<ExecuteResult>
+    where
+        'a: 'b,
+    {
+        self.connection.flush_stream().await?;
+
+        let query = query.into();
+        let sql_preview = truncate_sql_preview(&query);
+
+        let req = BatchRequest::new(query, self.connection.context().transaction_descriptor());
+
+        let id = self.connection.context_mut().next_packet_id();
+        self.connection.send(PacketHeader::batch(id), req).await?;
+        self.connection.record_statement();
+
+        ExecuteResult::new(&mut self.connection)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, "none".to_owned()))
+    }
+
+    /// Executes a `sqlcmd`/SSMS-style script, splitting it into batches on
+    /// `GO` separators and running each one sequentially with
+    /// [`simple_query`], draining its rows before moving on to the next
+    /// batch. `GO` is a client-side convention, not understood by the
+    /// server, so a script using it can't be sent as a single batch.
+    ///
+    /// Returns one result per batch, in order. If `stop_on_error` is
+    /// `false`, every batch runs regardless of earlier failures; if `true`,
+    /// execution stops at the first failing batch, whose error is the last
+    /// entry of the returned vector, and the batches after it get no entry.
+    ///
+    /// [`simple_query`]: #method.simple_query
+    pub async fn exec_script(
+        &mut self,
+        script: impl AsRef<str>,
+        stop_on_error: bool,
+    ) -> crate::Result<Vec<crate::Result<()>>> {
+        let mut results = Vec::new();
+
+        for batch in script::split_batches(script.as_ref()) {
+            let result = match self.simple_query(batch).await {
+                Ok(mut stream) => loop {
+                    match stream.try_next().await {
+                        Ok(Some(_)) => continue,
+                        Ok(None) => break Ok(()),
+                        Err(e) => break Err(e),
+                    }
+                },
+                Err(e) => Err(e),
+            };
+
+            let failed = result.is_err();
+            results.push(result);
+
+            if failed && stop_on_error {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Executes a DDL statement, e.g. `CREATE TABLE` or `ALTER INDEX` — a
+    /// small quality-of-life layer for migration tooling built on top of
+    /// this crate. Fails if the statement produces any result sets, since
+    /// DDL isn't expected to return rows, and calls `on_info` with every
+    /// informational message the server sends back, in arrival order.
+    ///
+    /// If the server rejects the statement because it can't run inside a
+    /// transaction (SQL Server error 226, e.g. `CREATE DATABASE` inside a
+    /// `BEGIN TRAN`), the returned error's
+    /// [`TokenError::is_ddl_in_transaction`] returns `true`, letting a
+    /// caller tell that failure apart from an ordinary syntax or permission
+    /// error.
+    ///
+    /// [`TokenError::is_ddl_in_transaction`]: struct.TokenError.html#method.is_ddl_in_transaction
+    pub async fn execute_ddl(
+        &mut self,
+        ddl: impl AsRef<str>,
+        mut on_info: impl FnMut(&TokenInfo),
+    ) -> crate::Result<()> {
+        self.connection.flush_stream().await?;
+
+        let req = BatchRequest::new(
+            ddl.as_ref(),
+            self.connection.context().transaction_descriptor(),
+        );
+
+        let id = self.connection.context_mut().next_packet_id();
+        self.connection.send(PacketHeader::batch(id), req).await?;
+        self.connection.record_statement();
+
+        let mut token_stream = TokenStream::new(&mut self.connection).try_unfold();
+
+        while let Some(token) = token_stream.try_next().await? {
+            match token {
+                ReceivedToken::NewResultset(_) | ReceivedToken::Row(_) => {
+                    return Err(Error::Protocol(
+                        "execute_ddl: statement unexpectedly returned a result set".into(),
+                    ))
+                }
+                ReceivedToken::Info(ref info) => on_info(info),
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts a SQL Server Agent job by name, via `msdb.dbo.sp_start_job`.
+    /// Starting a job is asynchronous on the server side; this returns as
+    /// soon as the server has accepted the request, not when the job
+    /// finishes. Poll [`job_status`] to watch it run.
+    ///
+    /// [`job_status`]: #method.job_status
+    pub async fn start_job(&mut self, job_name: &str) -> crate::Result<()> {
+        self.execute("EXEC msdb.dbo.sp_start_job @job_name = @P1", &[&job_name])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Fetches a SQL Server Agent job's current schedule/execution status
+    /// and the outcome of its most recent run, via
+    /// `msdb.dbo.sp_help_job`. Returns `None` if no job with that name
+    /// exists.
+    pub async fn job_status(&mut self, job_name: &str) -> crate::Result<Option<AgentJobStatus>> {
+        let stream = self
+            .query("EXEC msdb.dbo.sp_help_job @job_name = @P1", &[&job_name])
+            .await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(Some(agent::parse_job_status(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches a SQL Server Agent job's run history, most recent first, via
+    /// `msdb.dbo.sp_help_jobhistory`.
+    pub async fn job_history(
+        &mut self,
+        job_name: &str,
+    ) -> crate::Result<Vec<AgentJobHistoryEntry>> {
+        let stream = self
+            .query(
+                "EXEC msdb.dbo.sp_help_jobhistory @job_name = @P1",
+                &[&job_name],
+            )
+            .await?;
+
+        let rows = stream.into_first_result().await?;
+
+        rows.iter().map(agent::parse_job_history_entry).collect()
+    }
+
+    /// Drains up to `top` messages from a Service Broker queue, waiting up
+    /// to `timeout` for at least one message to arrive, via `WAITFOR
+    /// (RECEIVE ...)`. Returns an empty `Vec` if `timeout` elapses with no
+    /// message available.
+    ///
+    /// `queue` is spliced directly into the statement, the same way
+    /// [`bulk_insert`]'s `table` is, since `RECEIVE FROM` takes an object
+    /// name rather than a value SQL Server lets you parameterize - don't
+    /// pass it untrusted input.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, Client};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::{env, time::Duration};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = Client::connect(config, tcp.compat_write()).await?;
+    /// let messages = client
+    ///     .receive_service_broker_messages("OrderQueue", 32, Duration::from_secs(5))
+    ///     .await?;
+    ///
+    /// for message in &messages {
+    ///     println!("{}: {:?}", message.message_type_name(), message.body());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`bulk_insert`]: #method.bulk_insert
+    pub async fn receive_service_broker_messages(
+        &mut self,
+        queue: &str,
+        top: u32,
+        timeout: Duration,
+    ) -> crate::Result<Vec<ServiceBrokerMessage>> {
+        let sql = service_broker::build_receive_sql(queue, top, timeout);
+        let stream = self.simple_query(sql).await?;
+        let rows = stream.into_first_result().await?;
+
+        rows.iter().map(service_broker::parse_message).collect()
+    }
+
+    /// Describes a table's columns and indexes, assembled from `sys.columns`,
+    /// `sys.types`, `sys.default_constraints` and `sys.indexes`, for
+    /// schema-diff and migration tooling built on top of this crate.
+    ///
+    /// Unlike [`bulk_insert`] or [`receive_service_broker_messages`], `table`
+    /// is passed as a genuine query parameter to `OBJECT_ID`, not spliced
+    /// into the SQL text, so it's safe to pass user input here.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, Client};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = Client::connect(config, tcp.compat_write()).await?;
+    /// let table = client.describe_table("dbo.test").await?;
+    ///
+    /// for column in table.columns() {
+    ///     println!("{}: {}", column.name(), column.sql_type());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`bulk_insert`]: #method.bulk_insert
+    /// [`receive_service_broker_messages`]: #method.receive_service_broker_messages
+    pub async fn describe_table(&mut self, table: &str) -> crate::Result<TableDescription> {
+        let columns = self
+            .query(schema::COLUMNS_SQL, &[&table])
+            .await?
+            .into_first_result()
+            .await?
+            .iter()
+            .map(schema::parse_column)
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let index_rows = self
+            .query(schema::INDEXES_SQL, &[&table])
+            .await?
+            .into_first_result()
+            .await?;
+
+        let indexes = schema::parse_indexes(&index_rows)?;
+
+        Ok(schema::assemble(columns, indexes))
+    }
+
     /// Execute a `BULK INSERT` statement, efficiantly storing a large number of
     /// rows to a specified table. Note: make sure the input row follows the same
     /// schema as the table, otherwise calling `send()` will return an error.
@@ -349,17 +992,293 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
         BulkLoadRequest::new(&mut self.connection, columns)
     }
 
+    /// The SQL Server process ID (SPID) of this session, also obtainable
+    /// from within a query with `SELECT @@SPID`. Useful for correlating this
+    /// connection with server-side monitoring, or for passing to
+    /// [`Client::kill_session`] on another connection.
+    ///
+    /// [`Client::kill_session`]: #method.kill_session
+    pub fn spid(&self) -> u16 {
+        self.connection.spid()
+    }
+
+    /// The session affinity key set with [`Config::affinity_key`], if any.
+    /// Useful for confirming which shard or backend a sticky load balancer
+    /// actually routed this connection to.
+    ///
+    /// [`Config::affinity_key`]: crate::Config::affinity_key
+    pub fn affinity_key(&self) -> Option<&str> {
+        self.connection.affinity_key()
+    }
+
+    /// Which flavor of TDS server this connection is talking to. See
+    /// [`ServerKind`] for why this matters: Azure SQL Database, for
+    /// instance, doesn't support the same T-SQL surface as an on-premises
+    /// instance.
+    ///
+    /// [`ServerKind`]: crate::ServerKind
+    pub fn server_kind(&self) -> ServerKind {
+        self.connection.server_kind()
+    }
+
+    /// Applies this connection's [`Config::datetime_interpretation`] policy
+    /// to a `datetime`/`datetime2`/`smalldatetime` value read from a row,
+    /// resolving what its stored, timezone-less wall-clock time actually
+    /// means.
+    ///
+    /// [`FromSql`] can't do this itself: it has no way to see a
+    /// connection's configuration, so `row.get::<NaiveDateTime, _>(i)`
+    /// always stays exactly as ambiguous as the wire data. This method is
+    /// the explicit opt-in for callers who've set a policy and want it
+    /// applied.
+    ///
+    /// [`Config::datetime_interpretation`]: crate::Config::datetime_interpretation
+    /// [`FromSql`]: crate::FromSql
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "chrono")))]
+    pub fn interpret_datetime(
+        &self,
+        naive: crate::time::chrono::NaiveDateTime,
+    ) -> crate::time::chrono::InterpretedDateTime {
+        self.connection.datetime_interpretation().interpret(naive)
+    }
+
+    /// Switches the session's current database with `USE`.
+    ///
+    /// Not supported on [`ServerKind::AzureSqlDatabase`], which fixes the
+    /// database for the lifetime of the connection at login instead; pass
+    /// the desired database to [`Config::database`] before connecting
+    /// there. Calling this against Azure fails fast with
+    /// [`Error::Protocol`] instead of sending a request the server would
+    /// reject anyway.
+    ///
+    /// [`Config::database`]: crate::Config::database
+    pub async fn use_database(&mut self, database: impl Into<Cow<'_, str>>) -> crate::Result<()> {
+        if self.server_kind() == ServerKind::AzureSqlDatabase {
+            return Err(Error::Protocol(
+                "USE is not supported on Azure SQL Database; set Config::database before \
+                 connecting instead"
+                    .into(),
+            ));
+        }
+
+        let database = crate::pagination::quote_identifier(&database.into());
+        self.simple_query(format!("USE {}", database)).await?;
+
+        Ok(())
+    }
+
+    /// Terminates the given session on the server with `KILL`, requiring the
+    /// executing login to have the `ALTER ANY CONNECTION` permission. Useful
+    /// for administrative tooling that needs to manage other sessions
+    /// through the same crate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::Config;
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// client.kill_session(57).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn kill_session(&mut self, spid: u16) -> crate::Result<()> {
+        self.simple_query(format!("KILL {}", spid)).await?;
+        Ok(())
+    }
+
+    /// Impersonates `login` with `EXECUTE AS LOGIN`, returning a guard that
+    /// must be reverted explicitly with [`ImpersonationGuard::revert`] to
+    /// restore the connection's original security context; see the
+    /// [`impersonation`] module docs for why this can't happen automatically
+    /// on drop.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, Client};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = Client::connect(config, tcp.compat_write()).await?;
+    /// let guard = client.impersonate("low_privilege_login").await?;
+    /// // ... run statements as `low_privilege_login` ...
+    /// guard.revert().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`impersonation`]: crate::impersonation
+    pub async fn impersonate<'b>(
+        &'b mut self,
+        login: &str,
+    ) -> crate::Result<ImpersonationGuard<'b, S>> {
+        self.execute("EXECUTE AS LOGIN = @P1", &[&login]).await?;
+        Ok(ImpersonationGuard::new(self))
+    }
+
+    /// Begins a transaction, returning a guard used to run statements inside
+    /// it and, once done, [`commit`] or [`rollback`] it. See the
+    /// [`transaction`] module docs for [`Transaction::nested`], a
+    /// savepoint-based emulation of nested transactions.
+    ///
+    /// [`commit`]: crate::transaction::Transaction::commit
+    /// [`rollback`]: crate::transaction::Transaction::rollback
+    /// [`transaction`]: crate::transaction
+    /// [`Transaction::nested`]: crate::transaction::Transaction::nested
+    pub async fn transaction<'b>(&'b mut self) -> crate::Result<Transaction<'b, S>> {
+        self.execute_batch("BEGIN TRAN;").await?;
+        Ok(Transaction::new(self))
+    }
+
+    /// Asks the server to abort the batch or RPC this connection is
+    /// currently in the middle of, e.g. from a `tokio::select!` alongside a
+    /// timeout.
+    ///
+    /// Since a `Client` only allows one request in flight at a time, this
+    /// can't be called concurrently with the query it's meant to cancel from
+    /// a separate task — the borrow checker won't allow holding `&mut
+    /// Client` in two places at once. It's intended for cooperative
+    /// cancellation points a caller controls directly, such as between the
+    /// batches of [`exec_script`], not for interrupting a single in-flight
+    /// query from an OS signal handler running on another task.
+    ///
+    /// This crate doesn't implement a timeout of its own (see the
+    /// [`global_config`] module docs for why). Because `execute`, `query` and
+    /// friends already hold `&mut Client` for as long as they're in flight,
+    /// racing one against a timer on the same `Client` needs the timed-out
+    /// future dropped before `cancel` can borrow the client again — for
+    /// example a `select!` that owns the query future by value, or a loop
+    /// like `exec_script`'s that checks a deadline between statements and
+    /// calls `cancel` instead of starting the next one once it's passed.
+    ///
+    /// [`exec_script`]: #method.exec_script
+    /// [`global_config`]: crate::global_config
+    pub async fn cancel(&mut self) -> crate::Result<()> {
+        self.connection.cancel().await
+    }
+
+    /// A snapshot of usage counters (statements executed, rows read, bytes
+    /// sent/received, errors and uptime) accumulated on this connection so
+    /// far. Useful for a connection pool implementing least-used routing, or
+    /// for debugging which connection is doing the most work.
+    pub fn stats(&self) -> ConnectionStats {
+        self.connection.stats()
+    }
+
+    /// A snapshot of what was actually negotiated with the server during
+    /// `PRELOGIN`/`LOGIN7` - packet size, TDS version, encryption level and
+    /// MARS support. Useful for a pool or an application logging exactly
+    /// what it's talking to when debugging a mismatched environment.
+    pub fn negotiated(&self) -> NegotiatedSettings {
+        self.connection.negotiated()
+    }
+
+    /// Like [`ping`], but measures and returns how long the round trip took
+    /// instead of discarding it. Useful for a connection pool's health check
+    /// or a monitoring endpoint that wants a latency number distinct from
+    /// the semantics of any real query.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, Client};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = Client::connect(config, tcp.compat_write()).await?;
+    /// let rtt = client.ping_latency().await?;
+    /// println!("round trip took {:?}", rtt);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ping`]: #method.ping
+    pub async fn ping_latency(&mut self) -> crate::Result<Duration> {
+        let start = Instant::now();
+        self.execute("SELECT 1", &[]).await?;
+        Ok(start.elapsed())
+    }
+
+    /// Queries `sys.dm_exec_sessions` and `sys.dm_exec_requests` for the
+    /// current session (see [`Client#spid`]) and returns its current wait
+    /// type, blocking session and cumulative CPU/IO counters. Useful for an
+    /// application wanting to self-report why one of its own queries is
+    /// slow, without a separate monitoring connection.
+    ///
+    /// Requires `VIEW SERVER STATE` permission.
+    ///
+    /// [`Client#spid`]: #method.spid
+    pub async fn session_diagnostics(&mut self) -> crate::Result<SessionDiagnostics> {
+        let sql = "SELECT r.wait_type, r.blocking_session_id, s.cpu_time, \
+                    s.logical_reads, s.reads, s.writes \
+                    FROM sys.dm_exec_sessions s \
+                    LEFT JOIN sys.dm_exec_requests r ON r.session_id = s.session_id \
+                    WHERE s.session_id = @@SPID";
+
+        let row = self
+            .query(sql, &[])
+            .await?
+            .into_row()
+            .await?
+            .ok_or_else(|| {
+                crate::Error::Protocol("dm_exec_sessions returned no row for this session".into())
+            })?;
+
+        let wait_type: Option<&str> = row.try_get("wait_type")?;
+        let blocking_session_id: Option<i16> = row.try_get("blocking_session_id")?;
+        let cpu_time: i32 = row.try_get("cpu_time")?.unwrap_or_default();
+        let logical_reads: i64 = row.try_get("logical_reads")?.unwrap_or_default();
+        let reads: i64 = row.try_get("reads")?.unwrap_or_default();
+        let writes: i64 = row.try_get("writes")?.unwrap_or_default();
+
+        Ok(SessionDiagnostics::new(
+            wait_type.map(|s| s.to_owned()),
+            blocking_session_id,
+            cpu_time,
+            logical_reads,
+            reads,
+            writes,
+        ))
+    }
+
     pub(crate) fn rpc_params<'a>(query: impl Into<Cow<'a, str>>) -> Vec<RpcParam<'a>> {
         vec![
             RpcParam {
                 name: Cow::Borrowed("stmt"),
                 flags: BitFlags::empty(),
                 value: ColumnData::String(Some(query.into())),
+                type_info: None,
             },
             RpcParam {
                 name: Cow::Borrowed("params"),
                 flags: BitFlags::empty(),
                 value: ColumnData::I32(Some(0)),
+                type_info: None,
             },
         ]
     }
@@ -368,24 +1287,31 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
         &'a mut self,
         proc_id: RpcProcId,
         mut rpc_params: Vec<RpcParam<'b>>,
-        params: impl Iterator<Item = ColumnData<'b>>,
+        params: impl Iterator<Item = (ColumnData<'b>, Option<TypeInfo>)>,
     ) -> crate::Result<()>
     where
         'a: 'b,
     {
         let mut param_str = String::new();
 
-        for (i, param) in params.enumerate() {
+        for (i, (param, type_info)) in params.enumerate() {
             if i > 0 {
                 param_str.push(',')
             }
             param_str.push_str(&format!("@P{} ", i + 1));
-            param_str.push_str(&param.type_name());
+
+            match type_info {
+                Some(TypeInfo::VarLenSized(ref vlc)) if vlc.r#type() == VarLenType::BigVarChar => {
+                    param_str.push_str(&format!("varchar({})", vlc.len()));
+                }
+                _ => param_str.push_str(&param.type_name()),
+            }
 
             rpc_params.push(RpcParam {
                 name: Cow::Owned(format!("@P{}", i + 1)),
                 flags: BitFlags::empty(),
                 value: param,
+                type_info,
             });
         }
 
@@ -401,6 +1327,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Client<S> {
 
         let id = self.connection.context_mut().next_packet_id();
         self.connection.send(PacketHeader::rpc(id), req).await?;
+        self.connection.record_statement();
 
         Ok(())
     }
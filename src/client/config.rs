@@ -1,15 +1,16 @@
 mod ado_net;
 mod jdbc;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use super::AuthMethod;
+use super::{AuthMethod, Resolver};
 use crate::EncryptionLevel;
 use ado_net::*;
 use jdbc::*;
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 /// The `Config` struct contains all configuration information
 /// required for connecting to the database with a [`Client`]. It also provides
 /// the server address when connecting to a `TcpStream` via the
@@ -28,9 +29,52 @@ pub struct Config {
     pub(crate) database: Option<String>,
     pub(crate) instance_name: Option<String>,
     pub(crate) application_name: Option<String>,
+    pub(crate) affinity_key: Option<String>,
+    pub(crate) failover_partner: Option<String>,
+    pub(crate) multi_subnet_failover: bool,
+    pub(crate) readonly_intent: bool,
+    pub(crate) lenient_tokens: bool,
+    pub(crate) escalate_info_codes: Arc<HashSet<u32>>,
+    pub(crate) packet_size: u32,
     pub(crate) encryption: EncryptionLevel,
     pub(crate) trust: TrustConfig,
     pub(crate) auth: AuthMethod,
+    pub(crate) verify_database: bool,
+    pub(crate) new_password: Option<String>,
+    pub(crate) on_connect_sql: Option<String>,
+    pub(crate) resolver: Option<Arc<dyn Resolver>>,
+    #[cfg(feature = "chrono")]
+    pub(crate) datetime_interpretation: crate::time::chrono::DateTimeInterpretation,
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Config");
+        s.field("host", &self.host)
+            .field("port", &self.port)
+            .field("database", &self.database)
+            .field("instance_name", &self.instance_name)
+            .field("application_name", &self.application_name)
+            .field("affinity_key", &self.affinity_key)
+            .field("failover_partner", &self.failover_partner)
+            .field("multi_subnet_failover", &self.multi_subnet_failover)
+            .field("readonly_intent", &self.readonly_intent)
+            .field("lenient_tokens", &self.lenient_tokens)
+            .field("escalate_info_codes", &self.escalate_info_codes)
+            .field("packet_size", &self.packet_size)
+            .field("encryption", &self.encryption)
+            .field("trust", &self.trust)
+            .field("auth", &self.auth)
+            .field("verify_database", &self.verify_database)
+            .field("new_password", &self.new_password.is_some())
+            .field("on_connect_sql", &self.on_connect_sql)
+            .field("resolver", &self.resolver.is_some());
+
+        #[cfg(feature = "chrono")]
+        s.field("datetime_interpretation", &self.datetime_interpretation);
+
+        s.finish()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -42,12 +86,21 @@ pub(crate) enum TrustConfig {
 
 impl Default for Config {
     fn default() -> Self {
+        let global = crate::global_config::current();
+
         Self {
             host: None,
             port: None,
             database: None,
             instance_name: None,
             application_name: None,
+            affinity_key: None,
+            failover_partner: None,
+            multi_subnet_failover: false,
+            readonly_intent: false,
+            lenient_tokens: global.lenient_tokens,
+            escalate_info_codes: global.escalate_info_codes,
+            packet_size: global.packet_size,
             #[cfg(any(
                 feature = "rustls",
                 feature = "native-tls",
@@ -62,6 +115,12 @@ impl Default for Config {
             encryption: EncryptionLevel::NotSupported,
             trust: TrustConfig::Default,
             auth: AuthMethod::None,
+            verify_database: false,
+            new_password: None,
+            on_connect_sql: None,
+            resolver: None,
+            #[cfg(feature = "chrono")]
+            datetime_interpretation: crate::time::chrono::DateTimeInterpretation::default(),
         }
     }
 }
@@ -93,6 +152,100 @@ impl Config {
         self.database = Some(database.to_string())
     }
 
+    /// After login, checks that the session actually ended up in the
+    /// database set with [`database`], failing the connection attempt with
+    /// [`Error::DatabaseMismatch`] otherwise.
+    ///
+    /// This catches a login that doesn't have permission on the requested
+    /// database: rather than failing outright, SQL Server can silently fall
+    /// back to the login's default database (often `master`), and every
+    /// subsequent unqualified query then runs against the wrong database.
+    ///
+    /// Has no effect if [`database`] was never called.
+    ///
+    /// - Defaults to `false`.
+    ///
+    /// [`database`]: #method.database
+    /// [`Error::DatabaseMismatch`]: crate::error::Error::DatabaseMismatch
+    pub fn verify_database(&mut self, enable: bool) {
+        self.verify_database = enable;
+    }
+
+    /// Requests a password change as part of login, e.g. because the
+    /// account's current password has expired
+    /// (see [`TokenError::is_password_expired`]) and the server would
+    /// otherwise reject the login outright. The server processes the change
+    /// during `LOGIN7`, so a login that supplies both the current and the
+    /// new password can succeed in the same round trip.
+    ///
+    /// Only takes effect with [`AuthMethod::sql_server`]; other
+    /// authentication methods don't support changing a password this way
+    /// and ignore it.
+    ///
+    /// - Defaults to no password change requested.
+    ///
+    /// [`AuthMethod::sql_server`]: crate::AuthMethod::sql_server
+    /// [`TokenError::is_password_expired`]: crate::error::TokenError::is_password_expired
+    pub fn new_password(&mut self, new_password: impl ToString) {
+        self.new_password = Some(new_password.to_string());
+    }
+
+    /// A SQL script to run once, right after login, before the connection
+    /// is handed back to the caller. Useful for per-session setup such as
+    /// `SET` options or temp table initialization that every use of the
+    /// connection should see.
+    ///
+    /// The script is executed with [`Client::execute_batch`], so it can't
+    /// take parameters; delimit multiple statements with `;`.
+    ///
+    /// - Defaults to no script.
+    ///
+    /// [`Client::execute_batch`]: crate::Client::execute_batch
+    pub fn on_connect_sql(&mut self, sql: impl ToString) {
+        self.on_connect_sql = Some(sql.to_string());
+    }
+
+    /// Overrides how [`MultiSubnetFailover`] resolves the configured host,
+    /// letting an environment with its own service discovery — Consul, a
+    /// Kubernetes headless service, a SOCKS proxy — supply the addresses to
+    /// race a connection against instead of the OS resolver.
+    ///
+    /// The resolver is used by every [`MultiSubnetFailover`] connect, whether
+    /// or not [`multi_subnet_failover`] is set; a plain [`Client::connect`]
+    /// never resolves DNS itself, since the caller already supplies the
+    /// connected stream.
+    ///
+    /// - Defaults to the runtime's own resolver.
+    ///
+    /// [`MultiSubnetFailover`]: crate::MultiSubnetFailover
+    /// [`multi_subnet_failover`]: #method.multi_subnet_failover
+    /// [`Client::connect`]: crate::Client::connect
+    pub fn set_resolver(&mut self, resolver: impl Resolver + 'static) {
+        self.resolver = Some(Arc::new(resolver));
+    }
+
+    pub(crate) fn resolver(&self) -> Option<&Arc<dyn Resolver>> {
+        self.resolver.as_ref()
+    }
+
+    /// How a stored `datetime`/`datetime2`/`smalldatetime` value, which
+    /// carries no timezone on the wire, should be interpreted by
+    /// [`Client::interpret_datetime`].
+    ///
+    /// - Defaults to [`DateTimeInterpretation::Naive`], making no assumption
+    ///   about the stored value's timezone.
+    ///
+    /// [`Client::interpret_datetime`]: crate::Client::interpret_datetime
+    /// [`DateTimeInterpretation::Naive`]: crate::time::chrono::DateTimeInterpretation::Naive
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "chrono")))]
+    pub fn datetime_interpretation(
+        &mut self,
+        interpretation: crate::time::chrono::DateTimeInterpretation,
+    ) {
+        self.datetime_interpretation = interpretation;
+    }
+
     /// The instance name as defined in the SQL Browser. Only available on
     /// Windows platforms.
     ///
@@ -112,10 +265,147 @@ impl Config {
         self.application_name = Some(name.to_string());
     }
 
+    /// Embeds a session affinity key into the login record's `app_name`
+    /// field, e.g. `some-lb;affinity=shard-7`, for load balancers that
+    /// support routing new connections back to the same backend based on a
+    /// hint in the application name they can see during LOGIN7 - a pattern
+    /// otherwise hand-rolled by baking the key into
+    /// [`application_name`] directly.
+    ///
+    /// The key sent this way is available afterwards from
+    /// [`Client::affinity_key`].
+    ///
+    /// - Defaults to no affinity key set.
+    ///
+    /// [`application_name`]: Self::application_name
+    /// [`Client::affinity_key`]: crate::Client::affinity_key
+    pub fn affinity_key(&mut self, key: impl ToString) {
+        self.affinity_key = Some(key.to_string());
+    }
+
+    /// Sets the database mirroring failover partner, used as a fallback host
+    /// when the primary server named with [`host`] is unavailable. The
+    /// principal server also reports its current mirroring partner over the
+    /// wire, so following a failover this may need to be updated for
+    /// subsequent connection attempts.
+    ///
+    /// - Defaults to no failover partner specified.
+    ///
+    /// [`host`]: #method.host
+    pub fn failover_partner(&mut self, host: impl ToString) {
+        self.failover_partner = Some(host.to_string());
+    }
+
+    /// Get the failover partner address including port, if a failover
+    /// partner has been configured. Use this to retry the connection when
+    /// [`get_addr`] is unreachable.
+    ///
+    /// [`get_addr`]: #method.get_addr
+    pub fn get_failover_addr(&self) -> Option<String> {
+        self.failover_partner
+            .as_ref()
+            .map(|host| format!("{}:{}", host, self.get_port()))
+    }
+
+    /// Marks the host as an Always On Availability Group listener spanning
+    /// multiple subnets. [`MultiSubnetFailover::connect_multi_subnet_failover`]
+    /// resolves every IP behind [`host`] and races connection attempts
+    /// against all of them (staggered slightly so the common single-subnet
+    /// case doesn't waste sockets), instead of trying them one at a time,
+    /// which drastically reduces failover time after the listener moves to
+    /// a node on another subnet.
+    ///
+    /// The flag is only stored on the `Config`: no connect path reads it,
+    /// so `connect_multi_subnet_failover` races the resolved addresses
+    /// whether or not it is set, and `Client::connect` behaves the same
+    /// either way.
+    ///
+    /// - Defaults to `false`.
+    ///
+    /// [`host`]: #method.host
+    /// [`MultiSubnetFailover::connect_multi_subnet_failover`]: trait.MultiSubnetFailover.html#tymethod.connect_multi_subnet_failover
+    pub fn multi_subnet_failover(&mut self, enable: bool) {
+        self.multi_subnet_failover = enable;
+    }
+
+    /// Whether the host is configured as a multi-subnet Availability Group
+    /// listener, see [`multi_subnet_failover`].
+    ///
+    /// [`multi_subnet_failover`]: #method.multi_subnet_failover
+    pub fn get_multi_subnet_failover(&self) -> bool {
+        self.multi_subnet_failover
+    }
+
+    /// Marks the connection's application intent as read-only, so a
+    /// listener in front of an Always On Availability Group can route it to
+    /// a readable secondary replica instead of the primary. The server is
+    /// free to ignore this on a plain standalone instance.
+    ///
+    /// - Defaults to `false`.
+    pub fn readonly_intent(&mut self, enable: bool) {
+        self.readonly_intent = enable;
+    }
+
+    /// Controls how the token stream reacts to a token type it doesn't
+    /// recognize or doesn't know how to decode.
+    ///
+    /// - When `false` (the default), an unrecognized token fails the whole
+    ///   query with a protocol error. This is the safe choice: an unknown
+    ///   token usually means the wire format assumption underneath it no
+    ///   longer holds, and continuing to parse could silently desync the
+    ///   stream.
+    /// - When `true`, the token is instead skipped using its declared
+    ///   length and a warning is logged, letting the query continue. This
+    ///   trades that safety for being able to keep working against a newer
+    ///   server that sends token types this driver version predates.
+    pub fn lenient_tokens(&mut self, enable: bool) {
+        self.lenient_tokens = enable;
+    }
+
+    /// Escalates the given server message numbers (as seen on
+    /// [`TokenInfo::number`], e.g. 8152 for "String or binary data would be
+    /// truncated") from an informational message into a hard
+    /// [`Error::Server`], failing the query that triggered them instead of
+    /// letting it complete. Useful in strict data-quality pipelines where a
+    /// truncation or overflow warning should never be allowed to pass
+    /// silently.
+    ///
+    /// - Defaults to empty: no message number is escalated.
+    ///
+    /// [`TokenInfo::number`]: crate::TokenInfo::number
+    /// [`Error::Server`]: crate::error::Error::Server
+    pub fn escalate_info_codes(&mut self, codes: impl IntoIterator<Item = u32>) {
+        self.escalate_info_codes = Arc::new(codes.into_iter().collect());
+    }
+
+    /// The TDS packet size to request from the server during login. Larger
+    /// packets amortize per-packet header overhead over bulk data at the
+    /// cost of a bigger send/receive buffer; the server may negotiate this
+    /// down, which this crate picks up from its `EnvChange` response.
+    ///
+    /// - Defaults to `4096`, or to [`GlobalConfig::packet_size`] if
+    ///   [`set_global_defaults`] was called.
+    ///
+    /// [`GlobalConfig::packet_size`]: crate::GlobalConfig::packet_size
+    /// [`set_global_defaults`]: crate::set_global_defaults
+    pub fn packet_size(&mut self, packet_size: u32) {
+        self.packet_size = packet_size;
+    }
+
     /// Set the preferred encryption level.
     ///
-    /// - With `tls` feature, defaults to `Required`.
-    /// - Without `tls` feature, defaults to `NotSupported`.
+    /// This is sent as-is in the prelogin packet; the server replies with the
+    /// level it's willing to use and, unless that reply is
+    /// [`EncryptionLevel::NotSupported`], the connection is wrapped in TLS
+    /// before login. With [`EncryptionLevel::Off`], only the login itself
+    /// runs over TLS and the wrapper is torn back down to plain TCP right
+    /// after; every other level keeps TLS for the whole session. Requires one
+    /// of the `rustls`, `native-tls` or `vendored-openssl` features -
+    /// without one, only [`EncryptionLevel::NotSupported`] is possible and
+    /// the server is told encryption isn't available.
+    ///
+    /// - With a TLS backend feature enabled, defaults to `Required`.
+    /// - Without one, defaults to `NotSupported`.
     pub fn encryption(&mut self, encryption: EncryptionLevel) {
         self.encryption = encryption;
     }
@@ -182,7 +472,16 @@ impl Config {
 
     /// Get the host address including port
     pub fn get_addr(&self) -> String {
-        format!("{}:{}", self.get_host(), self.get_port())
+        let host = self.get_host();
+
+        // An IPv6 address needs bracketing (`[::1]:1433`) to be
+        // distinguishable from the port's separating colon; a hostname or
+        // IPv4 address never contains a colon, so this is unambiguous.
+        if host.contains(':') {
+            format!("[{}]:{}", host, self.get_port())
+        } else {
+            format!("{}:{}", host, self.get_port())
+        }
     }
 
     /// Creates a new `Config` from an [ADO.NET connection string].
@@ -202,6 +501,8 @@ impl Config {
     /// |`TrustServerCertificateCA`|`<path>`|Path to a `pem`, `crt` or `der` certificate file. Cannot be used together with `TrustServerCertificate`|
     /// |`encrypt`|`true`,`false`,`yes`,`no`,`DANGER_PLAINTEXT`|Specifies whether the driver uses TLS to encrypt communication.|
     /// |`Application Name`, `ApplicationName`|`<string>`|Sets the application name for the connection.|
+    /// |`Failover Partner`, `FailoverPartner`|`<string>`|The database mirroring failover partner host.|
+    /// |`MultiSubnetFailover`, `Multi Subnet Failover`|`true`,`false`,`yes`,`no`|Marks the host as an Availability Group listener spanning multiple subnets, racing connection attempts against all resolved IPs instead of trying them one at a time.|
     ///
     /// [ADO.NET connection string]: https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-strings
     pub fn from_ado_string(s: &str) -> crate::Result<Self> {
@@ -247,6 +548,14 @@ impl Config {
             builder.application_name(name);
         }
 
+        if let Some(host) = s.failover_partner() {
+            builder.failover_partner(host);
+        }
+
+        if s.multi_subnet_failover()? {
+            builder.multi_subnet_failover(true);
+        }
+
         if s.trust_cert()? {
             builder.trust_cert();
         }
@@ -261,6 +570,27 @@ impl Config {
     }
 }
 
+/// Parses a `Config` from an [ADO.NET connection string], same as
+/// [`Config::from_ado_string`] - lets a caller migrating from a driver that
+/// takes a bare connection string use `str::parse` or `TryFrom` instead of
+/// reaching for a `tiberius`-specific constructor.
+///
+/// ```
+/// # use tiberius::Config;
+/// let config: Config = "server=tcp:localhost,1433;user=sa;password=x".parse()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [ADO.NET connection string]: https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-strings
+/// [`Config::from_ado_string`]: Config::from_ado_string
+impl std::str::FromStr for Config {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Self::from_ado_string(s)
+    }
+}
+
 pub(crate) struct ServerDefinition {
     host: Option<String>,
     port: Option<u16>,
@@ -321,6 +651,13 @@ pub(crate) trait ConfigString {
             .map(|name| name.to_string())
     }
 
+    fn failover_partner(&self) -> Option<String> {
+        self.dict()
+            .get("failover partner")
+            .or_else(|| self.dict().get("failoverpartner"))
+            .map(|host| host.to_string())
+    }
+
     fn trust_cert(&self) -> crate::Result<bool> {
         self.dict()
             .get("trustservercertificate")
@@ -328,6 +665,14 @@ pub(crate) trait ConfigString {
             .unwrap_or(Ok(false))
     }
 
+    fn multi_subnet_failover(&self) -> crate::Result<bool> {
+        self.dict()
+            .get("multisubnetfailover")
+            .or_else(|| self.dict().get("multi subnet failover"))
+            .map(Self::parse_bool)
+            .unwrap_or(Ok(false))
+    }
+
     fn trust_cert_ca(&self) -> Option<String> {
         self.dict()
             .get("trustservercertificateca")
@@ -27,6 +27,15 @@ impl ConfigString for AdoNetConfig {
             })
         }
 
+        // Strips the brackets from a bracketed IPv6 literal (`[::1]` ->
+        // `::1`), so an IPv6 host is stored the same way whether or not the
+        // caller bracketed it. Non-bracketed values pass through unchanged.
+        fn strip_ipv6_brackets(host: &str) -> &str {
+            host.strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host)
+        }
+
         fn parse_server(parts: Vec<&str>) -> crate::Result<ServerDefinition> {
             if parts.is_empty() || parts.len() >= 3 {
                 return Err(crate::Error::Conversion("Server value faulty.".into()));
@@ -44,7 +53,7 @@ impl ConfigString for AdoNetConfig {
             } else {
                 // Connect using a TCP target
                 ServerDefinition {
-                    host: Some(parts[0].into()),
+                    host: Some(strip_ipv6_brackets(parts[0]).into()),
                     port: parse_port(&parts[1..])?,
                     instance: None,
                 }
@@ -188,6 +197,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn server_parsing_bracketed_ipv6() -> crate::Result<()> {
+        let test_str = "server=tcp:[::1],1433";
+        let ado: AdoNetConfig = test_str.parse()?;
+        let server = ado.server()?;
+
+        assert_eq!(Some("::1".to_string()), server.host);
+        assert_eq!(Some(1433), server.port);
+        assert_eq!(None, server.instance);
+
+        Ok(())
+    }
+
+    #[test]
+    fn server_parsing_bracketed_ipv6_no_port() -> crate::Result<()> {
+        let test_str = "server=tcp:[2001:db8::1]";
+        let ado: AdoNetConfig = test_str.parse()?;
+        let server = ado.server()?;
+
+        assert_eq!(Some("2001:db8::1".to_string()), server.host);
+        assert_eq!(None, server.port);
+        assert_eq!(None, server.instance);
+
+        Ok(())
+    }
+
     #[test]
     fn database_parsing() -> crate::Result<()> {
         let test_str = "database=Foo";
@@ -208,6 +243,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn failover_partner_parsing() -> crate::Result<()> {
+        let test_str = "Failover Partner=my-mirror.com";
+        let ado: AdoNetConfig = test_str.parse()?;
+
+        assert_eq!(Some("my-mirror.com".to_string()), ado.failover_partner());
+
+        let test_str = "FailoverPartner=my-mirror.com";
+        let ado: AdoNetConfig = test_str.parse()?;
+
+        assert_eq!(Some("my-mirror.com".to_string()), ado.failover_partner());
+
+        Ok(())
+    }
+
+    #[test]
+    fn multi_subnet_failover_parsing() -> crate::Result<()> {
+        let test_str = "MultiSubnetFailover=true";
+        let ado: AdoNetConfig = test_str.parse()?;
+
+        assert_eq!(true, ado.multi_subnet_failover()?);
+
+        let test_str = "Multi Subnet Failover=true";
+        let ado: AdoNetConfig = test_str.parse()?;
+
+        assert_eq!(true, ado.multi_subnet_failover()?);
+
+        Ok(())
+    }
+
     #[test]
     fn trust_cert_parsing_true() -> crate::Result<()> {
         let test_str = "TrustServerCertificate=true";
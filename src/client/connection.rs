@@ -5,16 +5,19 @@
 ))]
 use crate::client::{tls::TlsPreloginWrapper, tls_stream::create_tls_stream};
 use crate::{
-    client::{tls::MaybeTlsStream, AuthMethod, Config},
+    client::{
+        stats::StatsCounters, tls::MaybeTlsStream, AuthMethod, Config, ConnectionStats,
+        NegotiatedSettings,
+    },
     tds::{
         codec::{
             self, Encode, LoginMessage, Packet, PacketCodec, PacketHeader, PacketStatus,
-            PreloginMessage, TokenDone,
+            PacketType, PreloginMessage, TokenDone,
         },
         stream::TokenStream,
         Context, HEADER_BYTES,
     },
-    EncryptionLevel, SqlReadBytes,
+    EncryptionLevel, Error, ServerKind, SqlReadBytes,
 };
 use asynchronous_codec::Framed;
 use bytes::BytesMut;
@@ -31,12 +34,56 @@ use libgssapi::{
 use pretty_hex::*;
 #[cfg(all(unix, feature = "integrated-auth-gssapi"))]
 use std::ops::Deref;
-use std::{cmp, fmt::Debug, io, pin::Pin, task};
+use std::{cmp, fmt::Debug, io, mem, pin::Pin, task};
 use task::Poll;
 use tracing::{event, Level};
 #[cfg(all(windows, feature = "winauth"))]
 use winauth::{windows::NtlmSspiBuilder, NextBytes};
 
+/// The starting capacity for [`Connection`]'s reusable read/write buffers.
+/// Matches the default negotiated packet size, which covers most requests
+/// without a single resize.
+const DEFAULT_BUF_CAPACITY: usize = 4096;
+
+/// If a reusable buffer grows past this after a query (e.g. a large batch or
+/// row), it's replaced with a fresh, [`DEFAULT_BUF_CAPACITY`]-sized one
+/// instead of being kept around, so one oversized query doesn't pin that
+/// memory for the lifetime of the connection.
+const MAX_REUSABLE_BUF_CAPACITY: usize = 1024 * 1024;
+
+/// Appends `affinity_key` to `application_name` (`Config::affinity_key`'s
+/// implementation), so a load balancer inspecting the LOGIN7 packet's
+/// `app_name` field can see both the caller-supplied name and the affinity
+/// hint.
+fn combine_app_name(
+    application_name: Option<String>,
+    affinity_key: Option<&str>,
+) -> Option<String> {
+    match (application_name, affinity_key) {
+        (Some(name), Some(key)) => Some(format!("{};affinity={}", name, key)),
+        (Some(name), None) => Some(name),
+        (None, Some(key)) => Some(format!("affinity={}", key)),
+        (None, None) => None,
+    }
+}
+
+/// Bundles the options [`Connection::login`] needs, so adding one doesn't
+/// grow the function's argument list. Most of these come straight from
+/// [`Config`]; `encryption` and `prelogin` don't, since they're only known
+/// once the `PRELOGIN` round trip (and the TLS handshake it may trigger)
+/// has completed.
+struct LoginOptions {
+    auth: AuthMethod,
+    encryption: EncryptionLevel,
+    db: Option<String>,
+    server_name: Option<String>,
+    application_name: Option<String>,
+    readonly_intent: bool,
+    packet_size: u32,
+    new_password: Option<String>,
+    prelogin: PreloginMessage,
+}
+
 /// A `Connection` is an abstraction between the [`Client`] and the server. It
 /// can be used as a `Stream` to fetch [`Packet`]s from and to `send` packets
 /// splitting them to the negotiated limit automatically.
@@ -44,6 +91,11 @@ use winauth::{windows::NtlmSspiBuilder, NextBytes};
 /// `Connection` is not meant to use directly, but as an abstraction layer for
 /// the numerous `Stream`s for easy packet handling.
 ///
+/// `Connection` is generic over its transport `S` rather than boxing it, so
+/// the same protocol code compiles against any `AsyncRead + AsyncWrite`
+/// implementation - `tokio`'s `TcpStream`, a named pipe, a TLS stream, or a
+/// runtime-agnostic wrapper - without a separate code path per transport.
+///
 /// [`Client`]: struct.Encode.html
 /// [`Packet`]: ../protocol/codec/struct.Packet.html
 pub(crate) struct Connection<S>
@@ -54,6 +106,23 @@ where
     flushed: bool,
     context: Context,
     buf: BytesMut,
+    write_buf: BytesMut,
+    stats: StatsCounters,
+    /// The type of the message currently being assembled from physical
+    /// packets, or `None` between messages. Used to catch a stray packet of
+    /// the wrong type - e.g. a `PreLogin` packet showing up while a
+    /// `TabularResult` message is still in progress - as a protocol error
+    /// instead of silently feeding it to the token parser.
+    ///
+    /// The packet header's `id` isn't checked here: [MS-TDS] documents it as
+    /// a debug aid only, and this crate's own [`send`] reuses a single id,
+    /// unchanged, across every physical packet of one outgoing message
+    /// (only `status` toggles between packets) - a real server's multi-packet
+    /// responses follow the same convention, so an incrementing-id check
+    /// would reject ordinary result sets.
+    ///
+    /// [`send`]: Self::send
+    expected_continuation: Option<PacketType>,
 }
 
 impl<S: AsyncRead + AsyncWrite + Unpin + Send> Debug for Connection<S> {
@@ -68,11 +137,27 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Debug for Connection<S> {
 }
 
 impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
-    /// Creates a new connection
+    /// Creates a new connection.
+    ///
+    /// Establishing a session is inherently at least two round trips: the
+    /// client must see the server's PRELOGIN response (which carries the
+    /// negotiated encryption level and, for AAD, the nonce) before it can
+    /// build a correctly encrypted LOGIN7 packet, and a TLS handshake, when
+    /// negotiated, has to complete in between the two. There is no way to
+    /// piggyback PRELOGIN and LOGIN7 into a single round trip without
+    /// violating that ordering, so instead we just track and log how long
+    /// each half takes, which is useful when diagnosing a slow connect.
     pub(crate) async fn connect(config: Config, tcp_stream: S) -> crate::Result<Connection<S>> {
         let context = {
             let mut context = Context::new();
             context.set_spn(config.get_host(), config.get_port());
+            context.set_packet_size(config.packet_size);
+            context.set_lenient_tokens(config.lenient_tokens);
+            context.set_escalate_info_codes(config.escalate_info_codes.clone());
+            context.set_host_info(config.get_host().to_owned(), config.instance_name.clone());
+            context.set_affinity_key(config.affinity_key.clone());
+            #[cfg(feature = "chrono")]
+            context.set_datetime_interpretation(config.datetime_interpretation);
             context
         };
 
@@ -83,6 +168,9 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
             context,
             flushed: false,
             buf: BytesMut::new(),
+            write_buf: BytesMut::with_capacity(DEFAULT_BUF_CAPACITY),
+            stats: StatsCounters::new(),
+            expected_continuation: None,
         };
 
         let fed_auth_required = if let AuthMethod::AADToken(_) = config.auth {
@@ -91,35 +179,106 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
             false
         };
 
+        let prelogin_start = std::time::Instant::now();
+
         let prelogin = connection
-            .prelogin(config.encryption, fed_auth_required)
+            .prelogin(
+                config.encryption,
+                fed_auth_required,
+                config.instance_name.as_deref(),
+            )
             .await?;
 
+        event!(
+            Level::DEBUG,
+            "PRELOGIN round trip took {:?}",
+            prelogin_start.elapsed()
+        );
+
         let encryption = prelogin.negotiated_encryption(config.encryption);
+        let mars = prelogin.mars;
 
         let connection = connection.tls_handshake(&config, encryption).await?;
 
+        let login_start = std::time::Instant::now();
+
+        let application_name =
+            combine_app_name(config.application_name, config.affinity_key.as_deref());
+
         let mut connection = connection
-            .login(
-                config.auth,
+            .login(LoginOptions {
+                auth: config.auth,
                 encryption,
-                config.database,
-                config.host,
-                config.application_name,
+                db: config.database,
+                server_name: config.host,
+                application_name,
+                readonly_intent: config.readonly_intent,
+                packet_size: config.packet_size,
+                new_password: config.new_password,
                 prelogin,
-            )
+            })
             .await?;
 
+        connection.context_mut().set_encryption(encryption);
+        connection.context_mut().set_mars(mars);
+
         connection.flush_done().await?;
 
+        event!(
+            Level::DEBUG,
+            "LOGIN7 round trip took {:?}",
+            login_start.elapsed()
+        );
+
         Ok(connection)
     }
 
+    /// Builds a `Connection` around an already-established transport,
+    /// skipping the `PRELOGIN`/`LOGIN7` handshake - for tests that only need
+    /// to exercise token-stream decoding against canned bytes.
+    #[cfg(test)]
+    pub(crate) fn for_test(tcp_stream: S, lenient_tokens: bool) -> Self {
+        let mut context = Context::new();
+        context.set_lenient_tokens(lenient_tokens);
+
+        Self {
+            transport: Framed::new(MaybeTlsStream::Raw(tcp_stream), PacketCodec),
+            context,
+            flushed: false,
+            buf: BytesMut::new(),
+            write_buf: BytesMut::with_capacity(DEFAULT_BUF_CAPACITY),
+            stats: StatsCounters::new(),
+            expected_continuation: None,
+        }
+    }
+
     /// Flush the incoming token stream until receiving `DONE` token.
     async fn flush_done(&mut self) -> crate::Result<TokenDone> {
         TokenStream::new(self).flush_done().await
     }
 
+    /// Sends an Attention signal, asking the server to stop processing the
+    /// currently running batch or RPC as soon as possible.
+    ///
+    /// The server acknowledges by finishing its response with a `DONE` token
+    /// carrying the `Attention` status bit, so this drains the stream until
+    /// that arrives, leaving the connection ready for the next request. Per
+    /// the TDS spec an Attention can be sent as soon as a request has been
+    /// submitted, without waiting for the server to finish responding to it.
+    ///
+    /// Only useful while a request is in flight; sending one on an otherwise
+    /// idle connection has no effect beyond the round trip.
+    pub(crate) async fn cancel(&mut self) -> crate::Result<()> {
+        let id = self.context.next_packet_id();
+        self.write_to_wire(PacketHeader::attention(id), BytesMut::new())
+            .await?;
+        self.flush_sink().await?;
+
+        self.flush_done().await?;
+
+        Ok(())
+    }
+
     #[cfg(any(windows, feature = "integrated-auth-gssapi"))]
     /// Flush the incoming token stream until receiving `SSPI` token.
     async fn flush_sspi(&mut self) -> crate::Result<TokenSspi> {
@@ -169,9 +328,12 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
         self.flushed = false;
         let packet_size = (self.context.packet_size() as usize) - HEADER_BYTES;
 
-        let mut payload = BytesMut::new();
+        let mut payload = mem::take(&mut self.write_buf);
+        payload.clear();
         item.encode(&mut payload)?;
 
+        self.stats.record_bytes_sent(payload.len());
+
         while !payload.is_empty() {
             let writable = cmp::min(payload.len(), packet_size);
             let split_payload = payload.split_to(writable);
@@ -193,6 +355,15 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
 
         self.flush_sink().await?;
 
+        // Give the buffer back for the next `send`, unless this query grew
+        // it unusually large; then start the next one fresh instead of
+        // pinning that memory for the rest of the connection's lifetime.
+        self.write_buf = if payload.capacity() > MAX_REUSABLE_BUF_CAPACITY {
+            BytesMut::with_capacity(DEFAULT_BUF_CAPACITY)
+        } else {
+            payload
+        };
+
         Ok(())
     }
 
@@ -228,7 +399,11 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
     /// Calling this will slow down the queries if stream is still dirty if all
     /// results are not handled.
     pub async fn flush_stream(&mut self) -> crate::Result<()> {
-        self.buf.truncate(0);
+        if self.buf.capacity() > MAX_REUSABLE_BUF_CAPACITY {
+            self.buf = BytesMut::with_capacity(DEFAULT_BUF_CAPACITY);
+        } else {
+            self.buf.truncate(0);
+        }
 
         if self.flushed {
             return Ok(());
@@ -256,6 +431,62 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
         self.flushed && self.buf.is_empty()
     }
 
+    /// The SQL Server process ID (SPID) of this session, as reported by the
+    /// server on the packets it has sent so far. `0` if no packet has been
+    /// received yet.
+    pub fn spid(&self) -> u16 {
+        self.context.spid()
+    }
+
+    /// The session affinity key set with [`Config::affinity_key`], if any.
+    ///
+    /// [`Config::affinity_key`]: crate::Config::affinity_key
+    pub fn affinity_key(&self) -> Option<&str> {
+        self.context.affinity_key()
+    }
+
+    /// A snapshot of the usage counters accumulated on this connection so
+    /// far.
+    pub fn stats(&self) -> ConnectionStats {
+        ConnectionStats::new(&self.stats)
+    }
+
+    /// A snapshot of what `PRELOGIN`/`LOGIN7` actually negotiated with the
+    /// server - packet size, TDS version, encryption and MARS.
+    pub fn negotiated(&self) -> NegotiatedSettings {
+        NegotiatedSettings::new(&self.context)
+    }
+
+    /// Which flavor of TDS server this connection ended up talking to, see
+    /// [`ServerKind`]. Only meaningful after login has completed.
+    ///
+    /// [`ServerKind`]: crate::ServerKind
+    pub fn server_kind(&self) -> ServerKind {
+        self.context.server_kind()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn datetime_interpretation(&self) -> crate::time::chrono::DateTimeInterpretation {
+        self.context.datetime_interpretation()
+    }
+
+    /// Marks that a statement (query, execute or RPC call) was sent over
+    /// this connection.
+    pub(crate) fn record_statement(&mut self) {
+        self.stats.record_statement();
+    }
+
+    /// Marks that a row was read from this connection.
+    pub(crate) fn record_row(&mut self) {
+        self.stats.record_row();
+    }
+
+    /// Marks that a server-side error (`TokenError`) was received on this
+    /// connection.
+    pub(crate) fn record_error(&mut self) {
+        self.stats.record_error();
+    }
+
     /// A message sent by the client to set up context for login. The server
     /// responds to a client PRELOGIN message with a message of packet header
     /// type 0x04 and with the packet data containing a PRELOGIN structure.
@@ -268,10 +499,12 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
         &mut self,
         encryption: EncryptionLevel,
         fed_auth_required: bool,
+        instance_name: Option<&str>,
     ) -> crate::Result<PreloginMessage> {
         let mut msg = PreloginMessage::new();
         msg.encryption = encryption;
         msg.fed_auth_required = fed_auth_required;
+        msg.instance_name = instance_name.map(ToOwned::to_owned);
 
         let id = self.context.next_packet_id();
         self.send(PacketHeader::pre_login(id), msg).await?;
@@ -284,16 +517,22 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
 
     /// Defines the login record rules with SQL Server. Authentication with
     /// connection options.
-    async fn login<'a>(
-        mut self,
-        auth: AuthMethod,
-        encryption: EncryptionLevel,
-        db: Option<String>,
-        server_name: Option<String>,
-        application_name: Option<String>,
-        prelogin: PreloginMessage,
-    ) -> crate::Result<Self> {
+    async fn login(mut self, options: LoginOptions) -> crate::Result<Self> {
+        let LoginOptions {
+            auth,
+            encryption,
+            db,
+            server_name,
+            application_name,
+            readonly_intent,
+            packet_size,
+            new_password,
+            prelogin,
+        } = options;
+
         let mut login_message = LoginMessage::new();
+        login_message.readonly_intent(readonly_intent);
+        login_message.packet_size(packet_size);
 
         if let Some(db) = db {
             login_message.db_name(db);
@@ -414,6 +653,10 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
                 login_message.user_name(auth.user());
                 login_message.password(auth.password());
 
+                if let Some(new_password) = new_password {
+                    login_message.change_password(new_password);
+                }
+
                 let id = self.context.next_packet_id();
                 self.send(PacketHeader::login(id), login_message).await?;
                 self = self.post_login_encryption(encryption);
@@ -444,7 +687,10 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
             event!(Level::INFO, "Performing a TLS handshake");
 
             let Self {
-                transport, context, ..
+                transport,
+                context,
+                stats,
+                ..
             } = self;
             let mut stream = match transport.release().0 {
                 MaybeTlsStream::Raw(tcp) => {
@@ -463,6 +709,9 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection<S> {
                 context,
                 flushed: false,
                 buf: BytesMut::new(),
+                write_buf: BytesMut::with_capacity(DEFAULT_BUF_CAPACITY),
+                stats,
+                expected_continuation: None,
             })
         } else {
             event!(
@@ -498,10 +747,33 @@ impl<S: AsyncRead + AsyncWrite + Unpin + Send> Stream for Connection<S> {
 
         match ready!(this.transport.try_poll_next_unpin(cx)) {
             Some(Ok(packet)) => {
+                if let Some(expected_ty) = this.expected_continuation.take() {
+                    if packet.packet_type() != expected_ty {
+                        this.stats.record_error();
+                        return Poll::Ready(Some(Err(Error::Protocol(
+                            format!(
+                                "expected a continuation of a {:?} message, got a {:?} packet",
+                                expected_ty,
+                                packet.packet_type()
+                            )
+                            .into(),
+                        ))));
+                    }
+                }
+
+                if !packet.is_last() {
+                    this.expected_continuation = Some(packet.packet_type());
+                }
+
                 this.flushed = packet.is_last();
+                this.context.set_spid(packet.spid());
+                this.stats.record_bytes_received(packet.payload_len());
                 Poll::Ready(Some(Ok(packet)))
             }
-            Some(Err(e)) => Poll::Ready(Some(Err(e))),
+            Some(Err(e)) => {
+                this.stats.record_error();
+                Poll::Ready(Some(Err(e)))
+            }
             None => Poll::Ready(None),
         }
     }
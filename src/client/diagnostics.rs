@@ -0,0 +1,69 @@
+/// A snapshot of server-side diagnostic information for the current
+/// session, as reported by `sys.dm_exec_sessions` and
+/// `sys.dm_exec_requests`, taken at the moment [`Client#session_diagnostics`]
+/// is called.
+///
+/// Useful for an application wanting to self-report why one of its own
+/// queries is slow, without needing a separate monitoring connection.
+///
+/// [`Client#session_diagnostics`]: struct.Client.html#method.session_diagnostics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionDiagnostics {
+    wait_type: Option<String>,
+    blocking_session_id: Option<i16>,
+    cpu_time: i32,
+    logical_reads: i64,
+    reads: i64,
+    writes: i64,
+}
+
+impl SessionDiagnostics {
+    pub(crate) fn new(
+        wait_type: Option<String>,
+        blocking_session_id: Option<i16>,
+        cpu_time: i32,
+        logical_reads: i64,
+        reads: i64,
+        writes: i64,
+    ) -> Self {
+        Self {
+            wait_type,
+            blocking_session_id,
+            cpu_time,
+            logical_reads,
+            reads,
+            writes,
+        }
+    }
+
+    /// The type of wait the currently executing request is waiting on, if
+    /// any, e.g. `PAGEIOLATCH_SH` or `LCK_M_S`.
+    pub fn wait_type(&self) -> Option<&str> {
+        self.wait_type.as_deref()
+    }
+
+    /// The SPID of the session blocking the current request, if any.
+    pub fn blocking_session_id(&self) -> Option<i16> {
+        self.blocking_session_id
+    }
+
+    /// Cumulative CPU time, in milliseconds, used by the session so far.
+    pub fn cpu_time(&self) -> i32 {
+        self.cpu_time
+    }
+
+    /// The number of logical reads performed by the session so far.
+    pub fn logical_reads(&self) -> i64 {
+        self.logical_reads
+    }
+
+    /// The number of physical reads performed by the session so far.
+    pub fn reads(&self) -> i64 {
+        self.reads
+    }
+
+    /// The number of writes performed by the session so far.
+    pub fn writes(&self) -> i64 {
+        self.writes
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{tds::Context, EncryptionLevel, FeatureLevel};
+
+/// A snapshot of what was actually negotiated with the server during
+/// `PRELOGIN`/`LOGIN7`, taken at the moment [`Client#negotiated`] is called.
+///
+/// Useful when debugging a mismatched environment - e.g. a pool member
+/// talking to an older replica that downgraded the TDS version, or an
+/// encryption level that ended up weaker than the [`Config`] asked for.
+///
+/// [`Client`]: struct.Client.html
+/// [`Client#negotiated`]: struct.Client.html#method.negotiated
+/// [`Config`]: crate::Config
+#[derive(Debug, Clone, Copy)]
+pub struct NegotiatedSettings {
+    packet_size: u32,
+    tds_version: FeatureLevel,
+    encryption: EncryptionLevel,
+    mars: bool,
+}
+
+impl NegotiatedSettings {
+    pub(crate) fn new(context: &Context) -> Self {
+        Self {
+            packet_size: context.packet_size(),
+            tds_version: context.version(),
+            encryption: context.encryption(),
+            mars: context.mars(),
+        }
+    }
+
+    /// The packet size, in bytes, negotiated for this connection. Every
+    /// message sent or received on the wire is split into packets of at
+    /// most this size.
+    pub fn packet_size(&self) -> u32 {
+        self.packet_size
+    }
+
+    /// The TDS version the server acknowledged in its `LOGINACK`.
+    pub fn tds_version(&self) -> FeatureLevel {
+        self.tds_version
+    }
+
+    /// The encryption level negotiated in `PRELOGIN`.
+    pub fn encryption(&self) -> EncryptionLevel {
+        self.encryption
+    }
+
+    /// Whether the server offered Multiple Active Result Sets. This driver
+    /// never requests it, so a connection never actually uses it - this only
+    /// reflects what the server was willing to do.
+    pub fn mars(&self) -> bool {
+        self.mars
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use std::net::SocketAddr;
+
+/// A pluggable DNS resolver for [`MultiSubnetFailover`] connections, set via
+/// [`Config::set_resolver`].
+///
+/// By default, resolving the configured host is left to the runtime's own
+/// `lookup_host`/`resolve` call. Implementing this trait lets an environment
+/// with its own service discovery — Consul, a Kubernetes headless service, a
+/// SOCKS proxy — supply the addresses to race instead, without having to
+/// reimplement the staggered-connect behaviour itself.
+///
+/// [`MultiSubnetFailover`]: crate::MultiSubnetFailover
+/// [`Config::set_resolver`]: crate::Config::set_resolver
+#[async_trait]
+pub trait Resolver: Send + Sync {
+    /// Resolves `host` to the addresses [`MultiSubnetFailover`] should race
+    /// a connection against. `host` is the configured address including its
+    /// port, as returned by [`Config::get_addr`], not a bare host name.
+    ///
+    /// [`MultiSubnetFailover`]: crate::MultiSubnetFailover
+    /// [`Config::get_addr`]: crate::Config::get_addr
+    async fn resolve(&self, host: &str) -> std::io::Result<Vec<SocketAddr>>;
+}
@@ -0,0 +1,74 @@
+/// Splits a `sqlcmd`/SSMS-style script into individual batches on `GO`
+/// separators. SQL Server itself has no notion of `GO`; it's a purely
+/// client-side convention understood by migration tools, so a script
+/// generated by one of those tools can't be sent to the server as a single
+/// batch.
+///
+/// A line counts as a separator if, once trimmed, it equals `GO`
+/// case-insensitively. The optional repeat count some tools emit (`GO 5`) is
+/// not supported; such a line is treated as ordinary batch text. Empty
+/// batches (e.g. a script ending in `GO`, or consecutive `GO` lines) are
+/// dropped.
+pub(crate) fn split_batches(script: &str) -> Vec<String> {
+    let mut batches = Vec::new();
+    let mut current = String::new();
+
+    for line in script.lines() {
+        if line.trim().eq_ignore_ascii_case("go") {
+            if !current.trim().is_empty() {
+                batches.push(std::mem::take(&mut current));
+            } else {
+                current.clear();
+            }
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+
+    if !current.trim().is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_go() {
+        let script = "SELECT 1\nGO\nSELECT 2\nGO\nSELECT 3";
+        let batches = split_batches(script);
+
+        assert_eq!(3, batches.len());
+        assert_eq!("SELECT 1\n", batches[0]);
+        assert_eq!("SELECT 2\n", batches[1]);
+        assert_eq!("SELECT 3\n", batches[2]);
+    }
+
+    #[test]
+    fn go_is_case_insensitive_and_trimmed() {
+        let script = "SELECT 1\n  Go  \nSELECT 2";
+        let batches = split_batches(script);
+
+        assert_eq!(2, batches.len());
+        assert_eq!("SELECT 1\n", batches[0]);
+        assert_eq!("SELECT 2\n", batches[1]);
+    }
+
+    #[test]
+    fn drops_empty_batches() {
+        let script = "GO\nGO\nSELECT 1\nGO\nGO\n";
+        let batches = split_batches(script);
+
+        assert_eq!(vec!["SELECT 1\n"], batches);
+    }
+
+    #[test]
+    fn script_without_go_is_a_single_batch() {
+        let batches = split_batches("SELECT 1");
+        assert_eq!(vec!["SELECT 1\n"], batches);
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+/// A snapshot of usage counters for a single [`Client`] connection, taken at
+/// the moment [`Client#stats`] is called.
+///
+/// Useful for a connection pool wanting to implement least-used routing, or
+/// for an operator debugging which connection is doing the most work.
+///
+/// [`Client`]: struct.Client.html
+/// [`Client#stats`]: struct.Client.html#method.stats
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionStats {
+    statements_executed: u64,
+    rows_read: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+    errors: u64,
+    uptime: Duration,
+}
+
+impl ConnectionStats {
+    pub(crate) fn new(counters: &StatsCounters) -> Self {
+        Self {
+            statements_executed: counters.statements_executed,
+            rows_read: counters.rows_read,
+            bytes_sent: counters.bytes_sent,
+            bytes_received: counters.bytes_received,
+            errors: counters.errors,
+            uptime: counters.connected_at.elapsed(),
+        }
+    }
+
+    /// The number of statements (queries, executes and RPC calls) sent over
+    /// this connection.
+    pub fn statements_executed(&self) -> u64 {
+        self.statements_executed
+    }
+
+    /// The number of rows read from the server.
+    pub fn rows_read(&self) -> u64 {
+        self.rows_read
+    }
+
+    /// The number of payload bytes sent to the server.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// The number of payload bytes received from the server.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// The number of errors seen on this connection: `TokenError`s
+    /// (server-side errors), plus protocol and transport errors hit while
+    /// reading packets.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// How long the connection has been open.
+    pub fn uptime(&self) -> Duration {
+        self.uptime
+    }
+}
+
+/// The mutable counters accumulated over the lifetime of a `Connection`,
+/// from which a [`ConnectionStats`] snapshot is built on demand.
+#[derive(Debug)]
+pub(crate) struct StatsCounters {
+    statements_executed: u64,
+    rows_read: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+    errors: u64,
+    connected_at: Instant,
+}
+
+impl StatsCounters {
+    pub(crate) fn new() -> Self {
+        Self {
+            statements_executed: 0,
+            rows_read: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            errors: 0,
+            connected_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn record_statement(&mut self) {
+        self.statements_executed += 1;
+    }
+
+    pub(crate) fn record_row(&mut self) {
+        self.rows_read += 1;
+    }
+
+    pub(crate) fn record_bytes_sent(&mut self, len: usize) {
+        self.bytes_sent += len as u64;
+    }
+
+    pub(crate) fn record_bytes_received(&mut self, len: usize) {
+        self.bytes_received += len as u64;
+    }
+
+    pub(crate) fn record_error(&mut self) {
+        self.errors += 1;
+    }
+}
@@ -1,6 +1,15 @@
 use crate::Config;
 use futures::{AsyncRead, AsyncWrite};
 
+#[cfg(any(
+    all(feature = "native-tls", feature = "rustls"),
+    all(feature = "native-tls", feature = "vendored-openssl"),
+    all(feature = "rustls", feature = "vendored-openssl"),
+))]
+compile_error!(
+    "the `native-tls`, `rustls` and `vendored-openssl` features are mutually exclusive: enable exactly one TLS backend"
+);
+
 #[cfg(feature = "native-tls")]
 mod native_tls_stream;
 
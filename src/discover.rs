@@ -0,0 +1,114 @@
+//! Discovery of SQL Server instances through the SQL Server Browser service
+//! (SSRP, MS-SQLR), useful for tooling and for resolving the
+//! `host\instance` connect path ahead of time.
+
+#[cfg(feature = "sql-browser-tokio")]
+/// Tokio-based instance discovery.
+pub mod tokio;
+
+#[cfg(feature = "sql-browser-async-std")]
+/// async-std-based instance discovery.
+pub mod async_std;
+
+#[cfg(feature = "sql-browser-smol")]
+/// smol-based instance discovery.
+pub mod smol;
+
+/// Information about a single SQL Server instance, as reported by the SQL
+/// Server Browser service (SSRP, MS-SQLR).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlBrowserInstance {
+    server_name: String,
+    instance_name: String,
+    is_clustered: bool,
+    version: String,
+    tcp_port: Option<u16>,
+    np_pipe_name: Option<String>,
+}
+
+impl SqlBrowserInstance {
+    /// The name of the host machine running the instance.
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    /// The name of the SQL Server instance.
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    /// `true`, if the instance is part of a failover cluster.
+    pub fn is_clustered(&self) -> bool {
+        self.is_clustered
+    }
+
+    /// The reported server version, e.g. `15.00.2000.5`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The TCP port the instance is currently listening on, if enabled.
+    pub fn tcp_port(&self) -> Option<u16> {
+        self.tcp_port
+    }
+
+    /// The named pipe path of the instance, if enabled.
+    pub fn np_pipe_name(&self) -> Option<&str> {
+        self.np_pipe_name.as_deref()
+    }
+}
+
+#[cfg(any(
+    feature = "sql-browser-async-std",
+    feature = "sql-browser-tokio",
+    feature = "sql-browser-smol"
+))]
+fn parse_instances(buf: &[u8], len: usize) -> crate::Result<Vec<SqlBrowserInstance>> {
+    let err = || crate::Error::Conversion("Could not parse SQL browser response".into());
+
+    if len < 3 {
+        return Err(err());
+    }
+
+    let payload = std::str::from_utf8(&buf[3..len])?;
+    let mut instances = Vec::new();
+
+    for record in payload.split(";;").filter(|r| !r.is_empty()) {
+        let fields: Vec<&str> = record.split(';').collect();
+        let mut server_name = None;
+        let mut instance_name = None;
+        let mut is_clustered = false;
+        let mut version = None;
+        let mut tcp_port = None;
+        let mut np_pipe_name = None;
+
+        let mut iter = fields.chunks_exact(2);
+
+        for pair in &mut iter {
+            match pair[0] {
+                "ServerName" => server_name = Some(pair[1].to_string()),
+                "InstanceName" => instance_name = Some(pair[1].to_string()),
+                "IsClustered" => is_clustered = pair[1].eq_ignore_ascii_case("yes"),
+                "Version" => version = Some(pair[1].to_string()),
+                "tcp" => tcp_port = pair[1].parse().ok(),
+                "np" => np_pipe_name = Some(pair[1].to_string()),
+                _ => (),
+            }
+        }
+
+        if let (Some(server_name), Some(instance_name), Some(version)) =
+            (server_name, instance_name, version)
+        {
+            instances.push(SqlBrowserInstance {
+                server_name,
+                instance_name,
+                is_clustered,
+                version,
+                tcp_port,
+                np_pipe_name,
+            });
+        }
+    }
+
+    Ok(instances)
+}
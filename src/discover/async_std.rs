@@ -0,0 +1,31 @@
+use super::SqlBrowserInstance;
+use async_std::{io, net};
+use futures::TryFutureExt;
+use std::time;
+
+/// Broadcasts a `CLNT_UCAST_EX` request to the SQL Server Browser service
+/// running on `host` and returns the instances it knows about.
+///
+/// Requires the `sql-browser-async-std` feature.
+pub async fn instances(host: impl AsRef<str>) -> crate::Result<Vec<SqlBrowserInstance>> {
+    let local_bind: std::net::SocketAddr = "0.0.0.0:0".parse().unwrap();
+    let socket = net::UdpSocket::bind(&local_bind).await?;
+    socket.send_to(&[0x03], (host.as_ref(), 1434u16)).await?;
+
+    let mut buf = vec![0u8; 4096];
+    let timeout = time::Duration::from_millis(1000);
+
+    let len = io::timeout(timeout, socket.recv(&mut buf))
+        .map_err(|_| {
+            crate::Error::Conversion(
+                format!(
+                    "SQL browser timeout while enumerating instances on {}",
+                    host.as_ref(),
+                )
+                .into(),
+            )
+        })
+        .await?;
+
+    super::parse_instances(&buf, len)
+}
@@ -0,0 +1,42 @@
+use super::SqlBrowserInstance;
+use async_io::Timer;
+use async_net::UdpSocket;
+use futures::TryFutureExt;
+use futures_lite::FutureExt;
+use std::time::Duration;
+
+/// Broadcasts a `CLNT_UCAST_EX` request to the SQL Server Browser service
+/// running on `host` and returns the instances it knows about.
+///
+/// Requires the `sql-browser-smol` feature.
+pub async fn instances(host: impl AsRef<str>) -> crate::Result<Vec<SqlBrowserInstance>> {
+    let local_bind: std::net::SocketAddr = "0.0.0.0:0".parse().unwrap();
+    let socket = UdpSocket::bind(&local_bind).await?;
+    socket.send_to(&[0x03], (host.as_ref(), 1434u16)).await?;
+
+    let mut buf = vec![0u8; 4096];
+    let timeout = Duration::from_millis(1000);
+
+    let len = socket
+        .recv(&mut buf)
+        .or(async {
+            Timer::after(timeout).await;
+            Err(std::io::ErrorKind::TimedOut.into())
+        })
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::TimedOut {
+                crate::Error::Conversion(
+                    format!(
+                        "SQL browser timeout while enumerating instances on {}",
+                        host.as_ref(),
+                    )
+                    .into(),
+                )
+            } else {
+                e.into()
+            }
+        })
+        .await?;
+
+    super::parse_instances(&buf, len)
+}
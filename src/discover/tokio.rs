@@ -0,0 +1,36 @@
+use super::SqlBrowserInstance;
+use futures::TryFutureExt;
+use net::UdpSocket;
+use tokio::{
+    net,
+    time::{self, error::Elapsed, Duration},
+};
+
+/// Broadcasts a `CLNT_UCAST_EX` request to the SQL Server Browser service
+/// running on `host` and returns the instances it knows about.
+///
+/// Requires the `sql-browser-tokio` feature.
+pub async fn instances(host: impl AsRef<str>) -> crate::Result<Vec<SqlBrowserInstance>> {
+    let addr = (host.as_ref(), 1434u16);
+
+    let local_bind: std::net::SocketAddr = "0.0.0.0:0".parse().unwrap();
+    let socket = UdpSocket::bind(&local_bind).await?;
+    socket.send_to(&[0x03], addr).await?;
+
+    let mut buf = vec![0u8; 4096];
+    let timeout = Duration::from_millis(1000);
+
+    let len = time::timeout(timeout, socket.recv(&mut buf))
+        .map_err(|_: Elapsed| {
+            crate::Error::Conversion(
+                format!(
+                    "SQL browser timeout while enumerating instances on {}",
+                    host.as_ref(),
+                )
+                .into(),
+            )
+        })
+        .await??;
+
+    super::parse_instances(&buf, len)
+}
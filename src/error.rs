@@ -63,6 +63,192 @@ pub enum Error {
     #[error("BULK UPLOAD input failure: {0}")]
     /// Invalid input in Bulk Upload
     BulkInput(Cow<'static, str>),
+    #[error("Expected {} row(s), got {}", expected, actual)]
+    /// A result-shape helper such as [`QueryStream::into_single_row`] got a
+    /// different number of rows than it required.
+    ///
+    /// [`QueryStream::into_single_row`]: crate::QueryStream::into_single_row
+    UnexpectedRowCount {
+        /// What the caller required, e.g. `"exactly one"` or `"zero or one"`.
+        expected: Cow<'static, str>,
+        /// How many rows were actually returned.
+        actual: usize,
+    },
+    #[error("{source} (statement: `{sql}`, params: {params})")]
+    /// A [`Server`] error enriched with the offending SQL text (truncated
+    /// to a bounded length) and a summary of the bound parameter types, so
+    /// the error alone is actionable without correlating separate trace
+    /// lines.
+    ///
+    /// [`Server`]: Error::Server
+    Query {
+        /// The underlying error returned while executing the statement.
+        #[source]
+        source: Box<Error>,
+        /// The offending SQL text, truncated to a bounded length.
+        sql: String,
+        /// A summary of the bound parameter types, e.g. `"int, varchar(4000)"`,
+        /// or `"none"` if the statement took no parameters.
+        params: String,
+    },
+    #[error("{feature} is not supported by this driver: {hint}")]
+    /// The driver has recognized a wire value it deliberately doesn't
+    /// implement, such as a legacy or reserved TDS type. Distinct from
+    /// [`Error::Protocol`], which signals a malformed or unrecognized wire
+    /// value rather than a known one this driver hasn't implemented.
+    Unsupported {
+        /// The unsupported feature, e.g. `"UDT columns"`.
+        feature: Cow<'static, str>,
+        /// What to do about it, e.g. an alternative type or a feature flag
+        /// to enable.
+        hint: Cow<'static, str>,
+    },
+    #[error(
+        "Connected, but the effective database is `{}` instead of the requested `{}`; the \
+         login may lack permission to access it and silently landed in its default database",
+        actual,
+        requested
+    )]
+    /// [`Config#verify_database`] found that the session's database after
+    /// login didn't match the one requested, most commonly because the
+    /// login has no permission on it and SQL Server silently fell back to
+    /// its default database instead of failing the login outright.
+    ///
+    /// [`Config#verify_database`]: crate::Config::verify_database
+    DatabaseMismatch {
+        /// The database requested with [`Config::database`].
+        ///
+        /// [`Config::database`]: crate::Config::database
+        requested: String,
+        /// The database the session actually ended up in.
+        actual: String,
+    },
+}
+
+/// How many characters of the offending SQL text [`Error::Query`] keeps
+/// before truncating, so a large ad-hoc batch doesn't blow up a log line.
+const QUERY_ERROR_SQL_PREVIEW_LEN: usize = 512;
+
+/// Truncates `sql` to [`QUERY_ERROR_SQL_PREVIEW_LEN`] characters for
+/// inclusion in an [`Error::Query`].
+pub(crate) fn truncate_sql_preview(sql: &str) -> String {
+    if sql.chars().count() <= QUERY_ERROR_SQL_PREVIEW_LEN {
+        sql.to_owned()
+    } else {
+        let truncated: String = sql.chars().take(QUERY_ERROR_SQL_PREVIEW_LEN).collect();
+        format!("{}...", truncated)
+    }
+}
+
+/// Joins parameter type names into the summary used by [`Error::Query`],
+/// e.g. `"int, varchar(4000)"`, or `"none"` if there weren't any.
+pub(crate) fn describe_param_types<'a>(types: impl Iterator<Item = Cow<'a, str>>) -> String {
+    let mut out = String::new();
+
+    for (i, ty) in types.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&ty);
+    }
+
+    if out.is_empty() {
+        "none".to_owned()
+    } else {
+        out
+    }
+}
+
+impl Error {
+    /// Whether the operation that produced this error is likely to succeed
+    /// if retried, letting [`retry`] strategies and application code make
+    /// that decision without maintaining their own tables of SQL Server
+    /// error codes.
+    ///
+    /// This covers transient server-side errors (e.g. a deadlock or an
+    /// Azure SQL Database failover), a server-requested [`Routing`] to a
+    /// different address, and connection-level I/O errors that are safe to
+    /// reconnect and retry.
+    ///
+    /// [`retry`]: crate::retry
+    /// [`Routing`]: Error::Routing
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Query { source, .. } => source.is_transient(),
+            Self::Server(e) => e.is_transient(),
+            Self::Routing { .. } => true,
+            Self::Io { kind, .. } => matches!(
+                kind,
+                IoErrorKind::ConnectionReset
+                    | IoErrorKind::ConnectionAborted
+                    | IoErrorKind::BrokenPipe
+                    | IoErrorKind::TimedOut
+                    | IoErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the error is a constraint or index violation returned by the
+    /// server, such as a unique key, primary key or foreign key violation.
+    pub fn is_constraint_violation(&self) -> bool {
+        match self {
+            Self::Query { source, .. } => source.is_constraint_violation(),
+            Self::Server(e) => e.is_constraint_violation(),
+            _ => false,
+        }
+    }
+
+    /// Whether the statement was chosen as the deadlock victim and rolled
+    /// back by the server, i.e. SQL Server error 1205. Safe to retry.
+    pub fn is_deadlock_victim(&self) -> bool {
+        match self {
+            Self::Query { source, .. } => source.is_deadlock_victim(),
+            Self::Server(e) => e.is_deadlock_victim(),
+            _ => false,
+        }
+    }
+
+    /// Whether the error indicates that authentication with the server
+    /// failed, e.g. because of a bad login or password.
+    pub fn is_auth_failure(&self) -> bool {
+        match self {
+            Self::Query { source, .. } => source.is_auth_failure(),
+            Self::Server(e) => e.is_auth_failure(),
+            _ => false,
+        }
+    }
+
+    /// Whether the error is severe enough that the connection can no longer
+    /// be used and must be re-established, such as a fatal server error or
+    /// a broken I/O connection.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            Self::Query { source, .. } => source.is_fatal(),
+            Self::Server(e) => e.is_fatal(),
+            Self::Io { .. } | Self::Tls(_) => true,
+            #[cfg(any(all(unix, feature = "integrated-auth-gssapi"), doc))]
+            Self::Gssapi(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Wraps a [`Server`] error with the SQL text and parameter type summary
+    /// that produced it, turning it into an [`Error::Query`]. Any other kind
+    /// of error, including one that's already an [`Error::Query`], is
+    /// returned unchanged.
+    ///
+    /// [`Server`]: Error::Server
+    pub(crate) fn with_query_context(self, sql_preview: String, params: String) -> Self {
+        match self {
+            Self::Server(_) => Self::Query {
+                source: Box::new(self),
+                sql: sql_preview,
+                params,
+            },
+            other => other,
+        }
+    }
 }
 
 impl From<uuid::Error> for Error {
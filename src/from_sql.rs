@@ -60,13 +60,69 @@ where
 from_sql!(bool: ColumnData::Bit(val) => (*val, val));
 from_sql!(u8: ColumnData::U8(val) => (*val, val), ColumnData::I32(None) => (None, None));
 from_sql!(i16: ColumnData::I16(val) => (*val, val), ColumnData::U8(None) => (None, None), ColumnData::I32(None) => (None, None));
-from_sql!(i32: ColumnData::I32(val) => (*val, val), ColumnData::U8(None) => (None, None));
 from_sql!(i64: ColumnData::I64(val) => (*val, val), ColumnData::U8(None) => (None, None), ColumnData::I32(None) => (None, None));
-from_sql!(f32: ColumnData::F32(val) => (*val, val));
 from_sql!(f64: ColumnData::F64(val) => (*val, val));
 from_sql!(Uuid: ColumnData::Guid(val) => (*val, val));
 from_sql!(Numeric: ColumnData::Numeric(n) => (*n, n));
 
+/// Builds the error returned when a value from a wider column type does not
+/// fit into the narrower Rust type requested by the caller.
+fn narrowing_error(from: impl std::fmt::Debug, to: &str) -> crate::Error {
+    crate::Error::Conversion(format!("{:?} does not fit into an {}", from, to).into())
+}
+
+// `i32` additionally accepts `bigint` values that fit into the smaller
+// range, producing a `Conversion` error on overflow instead of silently
+// failing to match, which is what other drivers do when narrowing.
+impl<'a> FromSql<'a> for i32 {
+    fn from_sql(data: &'a ColumnData<'static>) -> crate::Result<Option<Self>> {
+        match data {
+            ColumnData::I32(val) => Ok(*val),
+            ColumnData::U8(None) => Ok(None),
+            ColumnData::I64(None) => Ok(None),
+            ColumnData::I64(Some(val)) => i32::try_from(*val)
+                .map(Some)
+                .map_err(|_| narrowing_error(val, "i32")),
+            v => Err(crate::Error::Conversion(
+                format!("cannot interpret {:?} as an i32 value", v).into(),
+            )),
+        }
+    }
+}
+
+impl FromSqlOwned for i32 {
+    fn from_sql_owned(data: ColumnData<'static>) -> crate::Result<Option<Self>> {
+        <i32 as FromSql>::from_sql(&data)
+    }
+}
+
+// `f32` additionally accepts `float(53)` (`f64`) values that fit into the
+// smaller range, producing a `Conversion` error on overflow.
+impl<'a> FromSql<'a> for f32 {
+    fn from_sql(data: &'a ColumnData<'static>) -> crate::Result<Option<Self>> {
+        match data {
+            ColumnData::F32(val) => Ok(*val),
+            ColumnData::F64(None) => Ok(None),
+            ColumnData::F64(Some(val)) => {
+                if val.is_finite() && (val.abs() > f32::MAX as f64) {
+                    Err(narrowing_error(val, "f32"))
+                } else {
+                    Ok(Some(*val as f32))
+                }
+            }
+            v => Err(crate::Error::Conversion(
+                format!("cannot interpret {:?} as an f32 value", v).into(),
+            )),
+        }
+    }
+}
+
+impl FromSqlOwned for f32 {
+    fn from_sql_owned(data: ColumnData<'static>) -> crate::Result<Option<Self>> {
+        <f32 as FromSql>::from_sql(&data)
+    }
+}
+
 impl FromSqlOwned for XmlData {
     fn from_sql_owned(value: ColumnData<'static>) -> crate::Result<Option<Self>> {
         match value {
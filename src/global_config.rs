@@ -0,0 +1,104 @@
+//! Process-wide defaults that new [`Config`]s inherit, set once via
+//! [`set_global_defaults`] before the first connection is made.
+//!
+//! Large applications with many call sites constructing a [`Config`] often
+//! want the same handful of knobs everywhere (the packet size to negotiate,
+//! whether to tolerate unknown tokens, which server warnings to escalate)
+//! without repeating them at every connect site or threading a shared
+//! builder through the whole codebase. Setting global defaults once at
+//! startup covers that case; an individual [`Config`] can still override any
+//! of them for itself.
+//!
+//! This intentionally doesn't cover a request timeout or a logging
+//! verbosity: this crate doesn't implement its own timeouts (callers wrap
+//! calls with e.g. `tokio::time::timeout` themselves) and doesn't have a
+//! log level of its own to control (it emits [`tracing`] events; verbosity
+//! is a property of whatever subscriber the application installs).
+//!
+//! [`Config`]: crate::Config
+//! [`tracing`]: https://docs.rs/tracing
+
+use once_cell::sync::OnceCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+static GLOBAL_DEFAULTS: OnceCell<GlobalConfig> = OnceCell::new();
+
+/// Builder for the process-wide defaults installed by
+/// [`set_global_defaults`]. See the [module docs] for what this does and
+/// doesn't cover.
+///
+/// [module docs]: self
+#[derive(Clone, Debug)]
+pub struct GlobalConfig {
+    pub(crate) packet_size: u32,
+    pub(crate) lenient_tokens: bool,
+    pub(crate) escalate_info_codes: Arc<HashSet<u32>>,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            packet_size: 4096,
+            lenient_tokens: false,
+            escalate_info_codes: Arc::new(HashSet::new()),
+        }
+    }
+}
+
+impl GlobalConfig {
+    /// Create a new `GlobalConfig` with the same defaults a `Config` would
+    /// otherwise use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The TDS packet size new connections request during login; see
+    /// [`Config::packet_size`].
+    ///
+    /// - Defaults to `4096`.
+    ///
+    /// [`Config::packet_size`]: crate::Config::packet_size
+    pub fn packet_size(&mut self, packet_size: u32) {
+        self.packet_size = packet_size;
+    }
+
+    /// See [`Config::lenient_tokens`].
+    ///
+    /// - Defaults to `false`.
+    ///
+    /// [`Config::lenient_tokens`]: crate::Config::lenient_tokens
+    pub fn lenient_tokens(&mut self, enable: bool) {
+        self.lenient_tokens = enable;
+    }
+
+    /// See [`Config::escalate_info_codes`].
+    ///
+    /// - Defaults to empty: no message number is escalated.
+    ///
+    /// [`Config::escalate_info_codes`]: crate::Config::escalate_info_codes
+    pub fn escalate_info_codes(&mut self, codes: impl IntoIterator<Item = u32>) {
+        self.escalate_info_codes = Arc::new(codes.into_iter().collect());
+    }
+}
+
+/// Installs process-wide defaults that every [`Config`] constructed from
+/// this point on (via [`Config::new`] or the `from_ado_string`/
+/// `from_jdbc_string` parsers) will start from, instead of this crate's
+/// built-in defaults. Meant to be called once, early in `main`, before any
+/// connection is made.
+///
+/// Returns the passed-in `GlobalConfig` back as an `Err` if defaults were
+/// already installed - this can only be set once per process, matching
+/// [`OnceCell::set`].
+///
+/// [`Config`]: crate::Config
+/// [`Config::new`]: crate::Config::new
+/// [`OnceCell::set`]: once_cell::sync::OnceCell::set
+pub fn set_global_defaults(config: GlobalConfig) -> Result<(), GlobalConfig> {
+    GLOBAL_DEFAULTS.set(config)
+}
+
+pub(crate) fn current() -> GlobalConfig {
+    GLOBAL_DEFAULTS.get().cloned().unwrap_or_default()
+}
@@ -0,0 +1,58 @@
+//! A guard for temporarily impersonating another login with `EXECUTE AS`,
+//! returned by [`Client::impersonate`].
+//!
+//! Rust has no asynchronous `Drop`, so this guard can't literally issue a
+//! `REVERT` when it goes out of scope the way a synchronous RAII guard
+//! would. Instead, callers must await [`ImpersonationGuard::revert`]
+//! explicitly; dropping the guard without calling it logs a warning instead
+//! of silently leaving the connection impersonated, since recovering from a
+//! caller forgetting to revert isn't something this crate can do on its own.
+//!
+//! [`Client::impersonate`]: crate::Client::impersonate
+
+use crate::Client;
+use futures::{AsyncRead, AsyncWrite};
+use tracing::{event, Level};
+
+/// Holds an impersonated `EXECUTE AS LOGIN` context on a [`Client`], created
+/// by [`Client::impersonate`]. Call [`revert`] once done with it to restore
+/// the connection's original security context; see the [module docs] for why
+/// dropping the guard instead only logs a warning.
+///
+/// [`Client`]: crate::Client
+/// [`Client::impersonate`]: crate::Client::impersonate
+/// [`revert`]: ImpersonationGuard::revert
+/// [module docs]: self
+#[must_use = "the impersonated context is only reverted by awaiting `revert`; dropping this guard leaves it in place and logs a warning"]
+#[derive(Debug)]
+pub struct ImpersonationGuard<'a, S: AsyncRead + AsyncWrite + Unpin + Send> {
+    client: Option<&'a mut Client<S>>,
+}
+
+impl<'a, S: AsyncRead + AsyncWrite + Unpin + Send> ImpersonationGuard<'a, S> {
+    pub(crate) fn new(client: &'a mut Client<S>) -> Self {
+        Self {
+            client: Some(client),
+        }
+    }
+
+    /// Issues `REVERT`, restoring the connection's original security
+    /// context.
+    pub async fn revert(mut self) -> crate::Result<()> {
+        let client = self.client.take().expect("client taken twice");
+        client.execute_batch("REVERT;").await?;
+        Ok(())
+    }
+}
+
+impl<'a, S: AsyncRead + AsyncWrite + Unpin + Send> Drop for ImpersonationGuard<'a, S> {
+    fn drop(&mut self) {
+        if self.client.is_some() {
+            event!(
+                Level::WARN,
+                "an ImpersonationGuard was dropped without calling `revert`; \
+                 the connection may still be impersonating a login"
+            );
+        }
+    }
+}
@@ -226,20 +226,40 @@
 //!
 //! # Other features
 //!
+//! - The [`prelude`] module re-exports the traits and types needed for the
+//!   common case of connecting, querying and reading rows.
 //! - If using an [ADO.NET connection string], it is possible to create a
 //!   [`Config`] from one. Please see the documentation for
-//!   [`from_ado_string`] for details.
+//!   [`from_ado_string`] for details. A [JDBC connection string], of the
+//!   form `jdbc:sqlserver://host\instance:1433;databaseName=x`, is also
+//!   supported, via [`from_jdbc_string`].
 //! - If wanting to use Tiberius with SQL Server version 2005, one must
 //!   disable the `tds73` feature.
+//! - Tiberius does not ship its own connection pool, so there is no
+//!   built-in equivalent of a `Pool::warm(n)`-style warm-up call. Use one
+//!   of the general-purpose asynchronous pools, such as
+//!   [bb8](https://crates.io/crates/bb8),
+//!   [mobc](https://crates.io/crates/mobc) or
+//!   [deadpool](https://crates.io/crates/deadpool), which already offer
+//!   configuring a minimum number of idle connections to avoid a latency
+//!   cliff on the first burst of traffic. [`Client#ping`] sends a minimal
+//!   no-op statement and is meant to be called from whatever periodic or
+//!   connection-check hook the pool crate provides, both to validate the
+//!   connection and to keep NAT/firewall mappings from expiring an
+//!   otherwise-idle socket.
 //!
 //! [`EncryptionLevel`]: enum.EncryptionLevel.html
 //! [`Client`]: struct.Client.html
 //! [`Client#query`]: struct.Client.html#method.query
 //! [`Client#execute`]: struct.Client.html#method.execute
+//! [`Client#ping`]: struct.Client.html#method.ping
 //! [`Query`]: struct.Query.html
 //! [`Query#bind`]: struct.Query.html#method.bind
 //! [`Config`]: struct.Config.html
 //! [`from_ado_string`]: struct.Config.html#method.from_ado_string
+//! [`from_jdbc_string`]: struct.Config.html#method.from_jdbc_string
+//! [JDBC connection string]: https://docs.microsoft.com/en-us/sql/connect/jdbc/building-the-connection-url?view=sql-server-ver15
+//! [`prelude`]: prelude/index.html
 //! [`time`]: time/index.html
 //! [ways of authentication]: enum.AuthMethod.html
 //! [ADO.NET connection string]: https://docs.microsoft.com/en-us/dotnet/framework/data/adonet/connection-strings
@@ -256,11 +276,27 @@ pub(crate) extern crate bigdecimal_ as bigdecimal;
 #[macro_use]
 mod macros;
 
+pub mod agent;
+pub mod analyze;
 mod client;
+pub mod discover;
+#[cfg(feature = "tds-codec")]
+pub mod framing;
 mod from_sql;
+pub mod global_config;
+pub mod impersonation;
+mod multi_subnet_failover;
+mod named_pipe;
+pub mod pagination;
 mod query;
+#[cfg(feature = "r2d2")]
+pub mod r2d2;
+pub mod retry;
+pub mod schema;
+pub mod service_broker;
 mod sql_read_bytes;
 mod to_sql;
+pub mod transaction;
 
 pub mod error;
 mod result;
@@ -269,28 +305,64 @@ mod tds;
 
 mod sql_browser;
 
-pub use client::{AuthMethod, Client, Config};
+pub use agent::{AgentJobHistoryEntry, AgentJobStatus};
+pub use client::{
+    AuthMethod, Client, Config, ConnectionStats, NegotiatedSettings, Resolver, SessionDiagnostics,
+};
 pub(crate) use error::Error;
+#[cfg(feature = "tds-codec")]
+pub use framing::{RawPacket, TdsCodec};
 pub use from_sql::{FromSql, FromSqlOwned};
-pub use query::Query;
+pub use global_config::{set_global_defaults, GlobalConfig};
+pub use impersonation::ImpersonationGuard;
+pub use multi_subnet_failover::MultiSubnetFailover;
+pub use named_pipe::NamedPipe;
+pub use pagination::paginated_query;
+pub use query::{Query, SetOption};
 pub use result::*;
-pub use row::{Column, ColumnType, Row};
+pub use row::{CaseSensitive, Column, ColumnType, RawRow, Row};
+pub use schema::{TableColumn, TableDescription, TableIndex};
+pub use service_broker::ServiceBrokerMessage;
 pub use sql_browser::SqlBrowser;
+#[cfg(feature = "tds-codec")]
+pub use tds::codec::PacketType;
 pub use tds::{
-    codec::{BulkLoadRequest, ColumnData, ColumnFlag, IntoRow, TokenRow, TypeLength},
+    codec::{
+        BulkLoadRequest, ColumnData, ColumnFlag, FeatureLevel, IntoRow, TokenInfo,
+        TokenReturnValue, TokenRow, TypeLength,
+    },
+    collation::Collation,
     numeric,
-    stream::QueryStream,
-    time, xml, EncryptionLevel,
+    stream::{QueryStream, RawQueryStream},
+    time, xml, EncryptionLevel, ServerKind,
 };
 pub use to_sql::{IntoSql, ToSql};
+pub use transaction::Transaction;
 pub use uuid::Uuid;
 
+/// A collection of the traits and types needed for the common case of
+/// connecting, querying and reading rows, so they can all be brought into
+/// scope with a single `use tiberius::prelude::*;` instead of naming each
+/// one individually.
+pub mod prelude {
+    pub use crate::{
+        AuthMethod, Client, Column, ColumnType, Config, FromSql, FromSqlOwned, IntoSql, Query, Row,
+        ToSql,
+    };
+}
+
 use sql_read_bytes::*;
 use tds::codec::*;
 
 /// An alias for a result that holds crate's error type as the error.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Not part of the public API. Exposed only so `benches/bench.rs` can
+/// measure the `nvarchar`/`nchar` UTF-16LE decode fast path, which otherwise
+/// lives behind the crate-private column data codec.
+#[doc(hidden)]
+pub use tds::codec::column_data::string::decode_utf16le as __bench_decode_utf16le;
+
 pub(crate) fn get_driver_version() -> u64 {
     env!("CARGO_PKG_VERSION")
         .splitn(6, '.')
@@ -300,3 +372,22 @@ pub(crate) fn get_driver_version() -> u64 {
             _ => acc | 0 << (part.0 * 8),
         })
 }
+
+/// Never called; exists purely so the compiler checks that the types an
+/// application is most likely to hand off between threads or tasks — rows,
+/// columns, query streams and execute results — stay `Send`. None of them
+/// hold an `Rc`, a `RefCell` or an unbounded trait object, so this should
+/// always hold, but it's cheap to pin down and would fail loudly if a future
+/// change broke it.
+#[allow(dead_code)]
+fn _assert_send<T: Send>() {}
+
+#[allow(dead_code)]
+fn _assert_result_types_are_send() {
+    _assert_send::<Row>();
+    _assert_send::<Column>();
+    _assert_send::<ExecuteResult>();
+    _assert_send::<QueryStream<'static>>();
+    _assert_send::<RawRow>();
+    _assert_send::<RawQueryStream<'static>>();
+}
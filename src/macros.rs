@@ -17,9 +17,10 @@ macro_rules! uint_enum {
 
         impl ::std::convert::TryFrom<u8> for $ty {
             type Error = ();
+            #[allow(unused_doc_comments)]
             fn try_from(n: u8) -> ::std::result::Result<$ty, ()> {
                 match n {
-                    $( x if x == $ty::$variant as u8 => Ok($ty::$variant), )*
+                    $( $( #[$attr] )* x if x == $ty::$variant as u8 => Ok($ty::$variant), )*
                     _ => Err(()),
                 }
             }
@@ -27,9 +28,10 @@ macro_rules! uint_enum {
 
         impl ::std::convert::TryFrom<u32> for $ty {
             type Error = ();
+            #[allow(unused_doc_comments)]
             fn try_from(n: u32) -> ::std::result::Result<$ty, ()> {
                 match n {
-                    $( x if x == $ty::$variant as u32 => Ok($ty::$variant), )*
+                    $( $( #[$attr] )* x if x == $ty::$variant as u32 => Ok($ty::$variant), )*
                     _ => Err(()),
                 }
             }
@@ -0,0 +1,52 @@
+#[cfg(feature = "multi-subnet-failover-tokio")]
+mod tokio;
+
+#[cfg(feature = "multi-subnet-failover-async-std")]
+mod async_std;
+
+#[cfg(feature = "multi-subnet-failover-smol")]
+mod smol;
+
+use crate::client::Config;
+use async_trait::async_trait;
+#[cfg(any(
+    feature = "multi-subnet-failover-async-std",
+    feature = "multi-subnet-failover-tokio",
+    feature = "multi-subnet-failover-smol"
+))]
+use std::time::Duration;
+
+/// The delay between staggered connection attempts to successive addresses,
+/// matching .NET's `MultiSubnetFailover` behaviour.
+#[cfg(any(
+    feature = "multi-subnet-failover-async-std",
+    feature = "multi-subnet-failover-tokio",
+    feature = "multi-subnet-failover-smol"
+))]
+const STAGGER_DELAY: Duration = Duration::from_millis(250);
+
+/// An extension trait to a `TcpStream` for connecting to an Always On
+/// Availability Group listener that spans multiple subnets.
+///
+/// A listener like this reports one DNS record per replica, all sharing the
+/// same name. Connecting to the addresses one at a time, as a plain
+/// `TcpStream::connect` on the resolved host would, means waiting out a full
+/// TCP timeout against every unreachable replica before reaching the one
+/// that's actually up. Instead, this races connection attempts against every
+/// resolved address at once (staggered by a short delay so the common case
+/// of a single reachable address doesn't open a pile of redundant sockets),
+/// and returns as soon as the first one succeeds.
+///
+/// The race runs whether or not [`Config::multi_subnet_failover`] is set;
+/// for a regular, single-subnet host it degenerates to a single connection
+/// attempt.
+///
+/// [`Config::multi_subnet_failover`]: struct.Config.html#method.multi_subnet_failover
+#[async_trait]
+pub trait MultiSubnetFailover {
+    /// Resolves every address behind the configured host and connects to
+    /// the fastest one to respond.
+    async fn connect_multi_subnet_failover(config: &Config) -> crate::Result<Self>
+    where
+        Self: Sized + Send + Sync;
+}
@@ -0,0 +1,44 @@
+use super::{MultiSubnetFailover, STAGGER_DELAY};
+use crate::client::Config;
+use async_std::{
+    net::{self, ToSocketAddrs},
+    task,
+};
+use async_trait::async_trait;
+use futures::future;
+use std::io;
+
+#[async_trait]
+impl MultiSubnetFailover for net::TcpStream {
+    /// This method can be used to connect to an Availability Group listener
+    /// spanning multiple subnets, when on the
+    /// `multi-subnet-failover-async-std` feature. Please see the crate
+    /// examples for more detailed examples.
+    async fn connect_multi_subnet_failover(config: &Config) -> crate::Result<Self> {
+        let addrs: Vec<_> = match config.resolver() {
+            Some(resolver) => resolver.resolve(&config.get_addr()).await?,
+            None => config.get_addr().to_socket_addrs().await?.collect(),
+        };
+
+        if addrs.is_empty() {
+            return Err(
+                io::Error::new(io::ErrorKind::NotFound, "Could not resolve server host").into(),
+            );
+        }
+
+        let attempts = addrs.into_iter().enumerate().map(|(i, addr)| {
+            Box::pin(async move {
+                task::sleep(STAGGER_DELAY * i as u32).await;
+                let stream = net::TcpStream::connect(addr).await?;
+                stream.set_nodelay(true)?;
+                Ok::<_, io::Error>(stream)
+            })
+        });
+
+        let (stream, _) = future::select_ok(attempts)
+            .await
+            .map_err(crate::Error::from)?;
+
+        Ok(stream)
+    }
+}
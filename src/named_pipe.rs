@@ -0,0 +1,24 @@
+#[cfg(all(windows, feature = "named-pipe-tokio"))]
+mod tokio;
+
+use crate::client::Config;
+use async_trait::async_trait;
+
+/// An extension trait to connect to SQL Server over a Windows named pipe
+/// instead of TCP.
+///
+/// Many on-prem SQL Server installations expose only named pipes
+/// (`\\.\pipe\sql\query` for the default instance, or
+/// `\\.\pipe\MSSQL$<INSTANCE>\sql\query` for a named one), with TCP
+/// disabled by policy. This trait is only implemented on Windows, behind
+/// the `named-pipe-tokio` feature.
+#[async_trait]
+pub trait NamedPipe {
+    /// Connects to the host in the given [`Config`] over a named pipe,
+    /// using the instance name, if set, to pick the right pipe path.
+    ///
+    /// [`Config`]: struct.Config.html
+    async fn connect_named_pipe(config: &Config) -> crate::Result<Self>
+    where
+        Self: Sized + Send + Sync;
+}
@@ -0,0 +1,43 @@
+use super::NamedPipe;
+use crate::client::Config;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::{net::windows::named_pipe::ClientOptions, time};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+/// Raw OS error code for `ERROR_PIPE_BUSY`, returned by `CreateFile` when
+/// every instance of the pipe is currently in use.
+const ERROR_PIPE_BUSY: i32 = 231;
+
+const RETRY_DELAY: Duration = Duration::from_millis(50);
+
+fn pipe_name(config: &Config) -> String {
+    match config.instance_name {
+        Some(ref instance) => {
+            format!(r"\\{}\pipe\MSSQL${}\sql\query", config.get_host(), instance)
+        }
+        None => format!(r"\\{}\pipe\sql\query", config.get_host()),
+    }
+}
+
+#[async_trait]
+impl NamedPipe for Compat<tokio::net::windows::named_pipe::NamedPipeClient> {
+    async fn connect_named_pipe(config: &Config) -> crate::Result<Self> {
+        let pipe_name = pipe_name(config);
+
+        let client = loop {
+            match ClientOptions::new().open(&pipe_name) {
+                Ok(client) => break client,
+                // All instances of the pipe are busy, wait a bit and retry
+                // the connection, the same way `CreateFile` callers are
+                // expected to on Windows.
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                    time::sleep(RETRY_DELAY).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        Ok(client.compat())
+    }
+}
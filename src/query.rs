@@ -3,15 +3,72 @@ use std::borrow::Cow;
 use futures::{AsyncRead, AsyncWrite};
 
 use crate::{
-    tds::{codec::RpcProcId, stream::TokenStream},
-    Client, ColumnData, ExecuteResult, IntoSql, QueryStream,
+    error::{describe_param_types, truncate_sql_preview},
+    tds::{
+        codec::{RpcProcId, TypeInfo, VarLenContext, VarLenType},
+        stream::TokenStream,
+    },
+    Client, ColumnData, ExecuteResult, IntoSql, QueryStream, SqlReadBytes,
 };
 
+/// The wire type a bound parameter should be forced to use, overriding the
+/// default [`ColumnData::type_name`] chosen when a parameter is sent without
+/// a known column context.
+#[derive(Debug, Clone, Copy)]
+enum ParamType {
+    Default,
+    Varchar,
+}
+
+/// The longest tag [`Query::tag`] will forward to the server, chosen to
+/// match the 128-byte limit of `CONTEXT_INFO`, the other place a DBA might
+/// look to attribute a session's load.
+const MAX_TAG_LEN: usize = 128;
+
+/// A session-level `SET` option that can be scoped to a single [`Query`]
+/// with [`Query::set_option`], letting a caller reproduce behavior SSMS
+/// applies by default (e.g. `ARITHABORT ON`) without changing it for the
+/// rest of the connection.
+///
+/// Kept as a closed enum, rather than a raw option name, so a caller can
+/// never smuggle arbitrary SQL into the batch through the option itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SetOption {
+    /// `SET ARITHABORT`. Affects whether an overflow or divide-by-zero
+    /// aborts the query, and is part of the plan cache key, so a mismatch
+    /// against SSMS's default of `ON` can lead to a different query plan.
+    ArithAbort,
+    /// `SET ANSI_NULLS`.
+    AnsiNulls,
+    /// `SET ANSI_WARNINGS`.
+    AnsiWarnings,
+    /// `SET QUOTED_IDENTIFIER`.
+    QuotedIdentifier,
+    /// `SET NOCOUNT`.
+    NoCount,
+}
+
+impl SetOption {
+    fn as_str(self) -> &'static str {
+        match self {
+            SetOption::ArithAbort => "ARITHABORT",
+            SetOption::AnsiNulls => "ANSI_NULLS",
+            SetOption::AnsiWarnings => "ANSI_WARNINGS",
+            SetOption::QuotedIdentifier => "QUOTED_IDENTIFIER",
+            SetOption::NoCount => "NOCOUNT",
+        }
+    }
+}
+
 /// A query object with bind parameters.
 #[derive(Debug)]
 pub struct Query<'a> {
     sql: Cow<'a, str>,
-    params: Vec<ColumnData<'a>>,
+    tag: Option<String>,
+    set_options: Vec<(SetOption, bool)>,
+    fast_rows: Option<u32>,
+    params: Vec<(ColumnData<'a>, ParamType)>,
 }
 
 impl<'a> Query<'a> {
@@ -24,15 +81,183 @@ impl<'a> Query<'a> {
     pub fn new(sql: impl Into<Cow<'a, str>>) -> Self {
         Self {
             sql: sql.into(),
+            tag: None,
+            set_options: Vec::new(),
+            fast_rows: None,
             params: Vec::new(),
         }
     }
 
+    /// Prepends a comment naming `tag` to the SQL batch sent to the server,
+    /// e.g. `/* checkout-service:order-123 */`, so the origin of a query
+    /// shows up next to it in `sys.dm_exec_query_stats`, a profiler trace or
+    /// a deadlock graph.
+    ///
+    /// `tag` is sanitized before being sent: any `*/` that would close the
+    /// comment early is broken up, control characters are dropped, and the
+    /// result is truncated to fit the server's `CONTEXT_INFO` limit of 128
+    /// bytes, so an attacker-influenced tag can't inject SQL into the batch.
+    ///
+    /// ```
+    /// # use tiberius::Query;
+    /// let mut query = Query::new("SELECT 1");
+    /// query.tag("checkout-service:order-123");
+    /// ```
+    pub fn tag(&mut self, tag: impl AsRef<str>) {
+        self.tag = Some(sanitize_tag(tag.as_ref()));
+    }
+
+    /// Sets a session-level `SET` option for the duration of this query only,
+    /// e.g. to reproduce SSMS's default of `ARITHABORT ON` in a driver, which
+    /// otherwise leaves the connection-level default of `OFF` in place and
+    /// can pick a different query plan than the one seen in SSMS.
+    ///
+    /// The option is emitted inside the batch executed by [`execute`] or
+    /// [`query`], so it is scoped to that batch and does not change the
+    /// setting for statements run later on the same connection. Calling this
+    /// more than once for the same [`SetOption`] emits the option that many
+    /// times; the server applies the last one.
+    ///
+    /// ```
+    /// # use tiberius::{Query, SetOption};
+    /// let mut query = Query::new("SELECT 1 / @P1");
+    /// query.set_option(SetOption::ArithAbort, true);
+    /// query.bind(0i32);
+    /// ```
+    ///
+    /// [`execute`]: Self::execute
+    /// [`query`]: Self::query
+    pub fn set_option(&mut self, option: SetOption, value: bool) {
+        self.set_options.push((option, value));
+    }
+
+    /// Appends an `OPTION (FAST n)` query hint, telling the optimizer to
+    /// pick a plan that returns the first `n` rows as quickly as possible,
+    /// at the cost of the total time to return every row - useful for a
+    /// data-grid UI that renders rows as they arrive and cares about
+    /// time-to-first-row more than total query time.
+    ///
+    /// The hint is only meaningful on a single `SELECT`; for a
+    /// multi-statement batch it applies to whichever statement the server
+    /// attaches a trailing `OPTION` clause to, which is not necessarily the
+    /// one the caller intended, so prefer running such a query on its own.
+    ///
+    /// ```
+    /// # use tiberius::Query;
+    /// let mut query = Query::new("SELECT * FROM ##Test ORDER BY id");
+    /// query.fast_first_rows(10);
+    /// ```
+    pub fn fast_first_rows(&mut self, n: u32) {
+        self.fast_rows = Some(n);
+    }
+
     /// Bind a new parameter to the query. Must be called exactly as many times
     /// as there are parameters in the given SQL. Otherwise the query will fail
     /// on execution.
     pub fn bind(&mut self, param: impl IntoSql<'a> + 'a) {
-        self.params.push(param.into_sql());
+        self.params.push((param.into_sql(), ParamType::Default));
+    }
+
+    /// Bind a new string parameter, forcing it to be sent to the server as
+    /// `varchar` instead of the `nvarchar` used by [`bind`]. Use this when a
+    /// column or `varchar`-typed procedure parameter should not be implicitly
+    /// converted to `nvarchar`, e.g. to avoid an index scan caused by a
+    /// collation mismatch between an `nvarchar` literal and a `varchar`
+    /// column.
+    ///
+    /// The parameter is encoded using the collation the server negotiated for
+    /// this connection at login.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn bind_varchar(&mut self, param: impl Into<Cow<'a, str>>) {
+        self.params
+            .push((ColumnData::String(Some(param.into())), ParamType::Varchar));
+    }
+
+    /// Like [`bind`], but consumes and returns `self`, letting parameters be
+    /// chained fluently instead of bound as separate statements:
+    ///
+    /// ```
+    /// # use tiberius::Query;
+    /// let query = Query::new("INSERT INTO ##Test (id, name) VALUES (@P1, @P2)")
+    ///     .push(1i32)
+    ///     .push("foo");
+    /// ```
+    ///
+    /// Each parameter is still assigned its `@PN` position by binding order,
+    /// so a heterogeneous parameter list can be built without collecting
+    /// `&dyn ToSql` trait objects into a slice first.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn push(mut self, param: impl IntoSql<'a> + 'a) -> Self {
+        self.bind(param);
+        self
+    }
+
+    /// Like [`push`], but for a forced `varchar` parameter, see
+    /// [`bind_varchar`].
+    ///
+    /// [`push`]: #method.push
+    /// [`bind_varchar`]: #method.bind_varchar
+    pub fn push_varchar(mut self, param: impl Into<Cow<'a, str>>) -> Self {
+        self.bind_varchar(param);
+        self
+    }
+
+    /// Returns the SQL batch to send to the server: the sanitized [`tag`]
+    /// prepended as a leading comment, followed by a `SET` statement for
+    /// every option passed to [`set_option`], followed by the query itself
+    /// and, if [`fast_first_rows`] was called, a trailing `OPTION (FAST n)`.
+    ///
+    /// [`tag`]: Self::tag
+    /// [`set_option`]: Self::set_option
+    /// [`fast_first_rows`]: Self::fast_first_rows
+    fn scoped_sql(&self) -> Cow<'a, str> {
+        if self.tag.is_none() && self.set_options.is_empty() && self.fast_rows.is_none() {
+            return self.sql.clone();
+        }
+
+        let mut sql = String::new();
+
+        if let Some(tag) = &self.tag {
+            sql.push_str(&format!("/* {} */\n", tag));
+        }
+
+        for (option, value) in &self.set_options {
+            let value = if *value { "ON" } else { "OFF" };
+            sql.push_str(&format!("SET {} {};\n", option.as_str(), value));
+        }
+
+        sql.push_str(&self.sql);
+
+        if let Some(n) = self.fast_rows {
+            sql.push_str(&format!(" OPTION (FAST {})", n));
+        }
+
+        Cow::Owned(sql)
+    }
+
+    /// Resolves the forced [`ParamType`]s into concrete [`TypeInfo`]s, using
+    /// the collation the connection negotiated at login.
+    fn resolve_params(
+        params: Vec<(ColumnData<'a>, ParamType)>,
+        collation: Option<crate::tds::Collation>,
+    ) -> Vec<(ColumnData<'a>, Option<TypeInfo>)> {
+        params
+            .into_iter()
+            .map(|(data, ty)| {
+                let type_info = match ty {
+                    ParamType::Default => None,
+                    ParamType::Varchar => Some(TypeInfo::VarLenSized(VarLenContext::new(
+                        VarLenType::BigVarChar,
+                        8000,
+                        collation,
+                    ))),
+                };
+
+                (data, type_info)
+            })
+            .collect()
     }
 
     /// Executes SQL statements in the SQL Server, returning the number rows
@@ -75,13 +300,22 @@ impl<'a> Query<'a> {
     {
         client.connection.flush_stream().await?;
 
-        let rpc_params = Client::<S>::rpc_params(self.sql);
+        let sql = self.scoped_sql();
+        let sql_preview = truncate_sql_preview(&sql);
+        let param_types = describe_param_types(self.params.iter().map(|(d, _)| d.type_name()));
+
+        let rpc_params = Client::<S>::rpc_params(sql);
+        let collation = client.connection.context().collation();
+        let params = Self::resolve_params(self.params, collation);
 
         client
-            .rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, self.params.into_iter())
-            .await?;
+            .rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, params.into_iter())
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
 
-        ExecuteResult::new(&mut client.connection).await
+        ExecuteResult::new(&mut client.connection)
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))
     }
 
     /// Executes SQL statements in the SQL Server, returning resulting rows.
@@ -123,16 +357,46 @@ impl<'a> Query<'a> {
         S: AsyncRead + AsyncWrite + Unpin + Send,
     {
         client.connection.flush_stream().await?;
-        let rpc_params = Client::<S>::rpc_params(self.sql);
+
+        let sql = self.scoped_sql();
+        let sql_preview = truncate_sql_preview(&sql);
+        let param_types = describe_param_types(self.params.iter().map(|(d, _)| d.type_name()));
+
+        let rpc_params = Client::<S>::rpc_params(sql);
+        let collation = client.connection.context().collation();
+        let params = Self::resolve_params(self.params, collation);
 
         client
-            .rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, self.params.into_iter())
-            .await?;
+            .rpc_perform_query(RpcProcId::ExecuteSQL, rpc_params, params.into_iter())
+            .await
+            .map_err(|e| e.with_query_context(sql_preview.clone(), param_types.clone()))?;
 
         let ts = TokenStream::new(&mut client.connection);
         let mut result = QueryStream::new(ts.try_unfold());
-        result.forward_to_metadata().await?;
+        result
+            .forward_to_metadata()
+            .await
+            .map_err(|e| e.with_query_context(sql_preview, param_types))?;
 
         Ok(result)
     }
 }
+
+/// Sanitizes a [`Query::tag`] so it can't break out of the `/* ... */`
+/// comment it's wrapped in or otherwise smuggle extra SQL into the batch:
+/// any `*/` is split apart, control characters (including newlines) are
+/// dropped, and the result is capped at [`MAX_TAG_LEN`] bytes.
+fn sanitize_tag(tag: &str) -> String {
+    let sanitized: String = tag
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .replace("*/", "* /");
+
+    let mut cut = sanitized.len().min(MAX_TAG_LEN);
+    while !sanitized.is_char_boundary(cut) {
+        cut -= 1;
+    }
+
+    sanitized[..cut].to_owned()
+}
@@ -0,0 +1,69 @@
+//! Optional integration with the [`r2d2`] connection pool.
+//!
+//! `r2d2::ManageConnection` is a synchronous trait: `connect` and
+//! `is_valid` return plain `Result`s, not futures. Tiberius has no blocking
+//! API, so [`ConnectionManager`] bridges the gap by driving a Tokio
+//! `TcpStream` connect, login and `SELECT 1` ping to completion with
+//! [`tokio::runtime::Handle::block_on`]. That means checkouts must happen
+//! somewhere blocking is allowed — [`tokio::task::spawn_blocking`] or
+//! [`tokio::task::block_in_place`] — rather than directly on the async task
+//! that will go on to use the connection.
+//!
+//! ```no_run
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! use tiberius::{r2d2::ConnectionManager, Config};
+//!
+//! let mut config = Config::new();
+//! config.host("localhost");
+//! config.authentication(tiberius::AuthMethod::sql_server("SA", "<YourStrong@Passw0rd>"));
+//!
+//! let manager = ConnectionManager::new(config);
+//! let pool = tokio::task::spawn_blocking(move || r2d2::Pool::builder().build(manager))
+//!     .await??;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{Client, Config, Error};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+/// An [`r2d2::ManageConnection`] that hands out [`Client`]s backed by a
+/// plain Tokio `TcpStream`. Reused across checkouts, so cloning a [`Config`]
+/// per connection is the only per-connect allocation.
+#[derive(Debug, Clone)]
+pub struct ConnectionManager {
+    config: Config,
+}
+
+impl ConnectionManager {
+    /// Creates a manager that connects using the given configuration.
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+}
+
+impl r2d2::ManageConnection for ConnectionManager {
+    type Connection = Client<Compat<TcpStream>>;
+    type Error = Error;
+
+    fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        tokio::runtime::Handle::current().block_on(async {
+            let tcp = TcpStream::connect(self.config.get_addr()).await?;
+            tcp.set_nodelay(true)?;
+
+            Client::connect(self.config.clone(), tcp.compat_write()).await
+        })
+    }
+
+    fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        tokio::runtime::Handle::current().block_on(async {
+            conn.simple_query("SELECT 1").await?.into_results().await?;
+            Ok(())
+        })
+    }
+
+    fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+        false
+    }
+}
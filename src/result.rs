@@ -1,10 +1,12 @@
-pub use crate::tds::stream::{QueryItem, ResultMetadata};
+pub use crate::tds::stream::{QueryItem, RawQueryItem, ResultMetadata};
 use crate::{
     client::Connection,
+    tds::codec::{TokenInfo, TokenReturnValue},
     tds::stream::{ReceivedToken, TokenStream},
+    Row,
 };
 use futures::{AsyncRead, AsyncWrite, TryStreamExt};
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 /// A result from a query execution, listing the number of affected rows.
 ///
@@ -44,36 +46,159 @@ use std::fmt::Debug;
 #[derive(Debug)]
 pub struct ExecuteResult {
     rows_affected: Vec<u64>,
+    return_values: Vec<TokenReturnValue>,
+    nested_results: Vec<ProcResult>,
 }
 
 impl<'a> ExecuteResult {
     pub(crate) async fn new<S: AsyncRead + AsyncWrite + Unpin + Send>(
         connection: &'a mut Connection<S>,
+    ) -> crate::Result<Self> {
+        Self::new_with_progress(connection, |_| ()).await
+    }
+
+    /// Like [`new`], but calling `on_info` with every `INFO` token received
+    /// along the way, e.g. a `PRINT` or a `RAISERROR ... WITH NOWAIT` a
+    /// long-running procedure used to report progress.
+    ///
+    /// [`new`]: #method.new
+    pub(crate) async fn new_with_progress<S: AsyncRead + AsyncWrite + Unpin + Send>(
+        connection: &'a mut Connection<S>,
+        mut on_info: impl FnMut(&TokenInfo),
     ) -> crate::Result<Self> {
         let token_stream = TokenStream::new(connection).try_unfold();
 
-        let rows_affected = token_stream
-            .try_fold(Vec::new(), |mut acc, token| async move {
-                match token {
-                    ReceivedToken::DoneProc(done) if done.is_final() => (),
-                    ReceivedToken::DoneProc(done) => acc.push(done.rows()),
-                    ReceivedToken::DoneInProc(done) => acc.push(done.rows()),
-                    ReceivedToken::Done(done) => acc.push(done.rows()),
-                    _ => (),
-                }
-                Ok(acc)
-            })
+        let (rows_affected, return_values, nested_results, _) = token_stream
+            .try_fold(
+                (Vec::new(), Vec::new(), Vec::new(), None),
+                |(mut rows_affected, mut return_values, mut nested_results, mut pending_status),
+                 token| {
+                    match token {
+                        ReceivedToken::DoneProc(done) if done.is_final() => (),
+                        ReceivedToken::DoneProc(done) => {
+                            rows_affected.push(done.rows());
+
+                            if let Some(status) = pending_status.take() {
+                                nested_results.push(ProcResult {
+                                    return_status: status,
+                                    rows_affected: done.rows(),
+                                });
+                            }
+                        }
+                        ReceivedToken::DoneInProc(done) => rows_affected.push(done.rows()),
+                        ReceivedToken::Done(done) => rows_affected.push(done.rows()),
+                        ReceivedToken::Info(ref info) => on_info(info),
+                        ReceivedToken::ReturnValue(retval) => return_values.push(retval),
+                        ReceivedToken::ReturnStatus(status) => pending_status = Some(status as i32),
+                        _ => (),
+                    }
+                    async move { Ok((rows_affected, return_values, nested_results, pending_status)) }
+                },
+            )
             .await?;
 
-        Ok(Self { rows_affected })
+        Ok(Self {
+            rows_affected,
+            return_values,
+            nested_results,
+        })
+    }
+
+    /// Like [`new`], but also collecting any rows the statement returned,
+    /// e.g. from an `OUTPUT` clause on an `INSERT`/`UPDATE`/`DELETE`. Plain
+    /// [`new`] silently drops such rows instead of erroring, since a
+    /// well-formed request never sends them; use this instead of [`new`]
+    /// whenever the statement might contain an `OUTPUT` clause.
+    ///
+    /// [`new`]: #method.new
+    pub(crate) async fn new_with_rows<S: AsyncRead + AsyncWrite + Unpin + Send>(
+        connection: &'a mut Connection<S>,
+    ) -> crate::Result<(Self, Vec<Row>)> {
+        let token_stream = TokenStream::new(connection).try_unfold();
+
+        let mut rows_affected = Vec::new();
+        let mut return_values = Vec::new();
+        let mut nested_results = Vec::new();
+        let mut pending_status: Option<i32> = None;
+        let mut rows = Vec::new();
+        let mut columns = None;
+        let mut result_index = None;
+
+        futures::pin_mut!(token_stream);
+
+        while let Some(token) = token_stream.try_next().await? {
+            match token {
+                ReceivedToken::DoneProc(done) if done.is_final() => (),
+                ReceivedToken::DoneProc(done) => {
+                    rows_affected.push(done.rows());
+
+                    if let Some(status) = pending_status.take() {
+                        nested_results.push(ProcResult {
+                            return_status: status,
+                            rows_affected: done.rows(),
+                        });
+                    }
+                }
+                ReceivedToken::DoneInProc(done) => rows_affected.push(done.rows()),
+                ReceivedToken::Done(done) => rows_affected.push(done.rows()),
+                ReceivedToken::Info(_) => (),
+                ReceivedToken::ReturnValue(retval) => return_values.push(retval),
+                ReceivedToken::ReturnStatus(status) => pending_status = Some(status as i32),
+                ReceivedToken::NewResultset(meta) => {
+                    columns = Some(Arc::new(meta.columns().collect::<Vec<_>>()));
+                    result_index = Some(result_index.map_or(0, |i: usize| i + 1));
+                }
+                ReceivedToken::Row(data) => rows.push(Row {
+                    columns: columns.clone().unwrap(),
+                    data,
+                    result_index: result_index.unwrap(),
+                }),
+                _ => (),
+            }
+        }
+
+        let execute_result = Self {
+            rows_affected,
+            return_values,
+            nested_results,
+        };
+
+        Ok((execute_result, rows))
     }
 
     /// A slice of numbers of rows affected in the same order as the given
-    /// queries.
+    /// queries. For a batch of several statements, this is one count per
+    /// statement, taken from that statement's own `DONEINPROC`/`DONE` token
+    /// rather than only the first or last one.
     pub fn rows_affected(&self) -> &[u64] {
         self.rows_affected.as_slice()
     }
 
+    /// The `OUTPUT` parameter values and user-defined function return values
+    /// (`RETURNVALUE` tokens) received for this request, in the order the
+    /// server sent them. A procedure's
+    /// `RETURN` status arrives as a separate `RETURNSTATUS` token and is not
+    /// part of this slice; see [`nested_results`].
+    ///
+    /// [`nested_results`]: #method.nested_results
+    pub fn return_values(&self) -> &[TokenReturnValue] {
+        self.return_values.as_slice()
+    }
+
+    /// The completions of any procedures called by this request, in the
+    /// order the server finished them.
+    ///
+    /// A stored procedure that calls another stored procedure sends a
+    /// `RETURNSTATUS`/`DONEPROC` pair for each level of nesting, innermost
+    /// first. A request that doesn't call a nested procedure produces an
+    /// empty slice here; its own completion is already reflected in
+    /// [`rows_affected`].
+    ///
+    /// [`rows_affected`]: #method.rows_affected
+    pub fn nested_results(&self) -> &[ProcResult] {
+        self.nested_results.as_slice()
+    }
+
     /// Aggregates all resulting row counts into a sum.
     ///
     /// # Example
@@ -106,6 +231,27 @@ impl<'a> ExecuteResult {
     }
 }
 
+/// The completion of one nested procedure call, pairing the `RETURN` value
+/// it sent back with the number of rows its last statement affected. See
+/// [`ExecuteResult::nested_results`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProcResult {
+    return_status: i32,
+    rows_affected: u64,
+}
+
+impl ProcResult {
+    /// The integer value the procedure passed to `RETURN`.
+    pub fn return_status(&self) -> i32 {
+        self.return_status
+    }
+
+    /// The number of rows affected by the procedure's last statement.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
 impl IntoIterator for ExecuteResult {
     type Item = u64;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -0,0 +1,122 @@
+//! Pluggable retry/backoff strategies.
+//!
+//! Rather than baking a single fixed policy into the driver, transient
+//! failures (a dropped login, a deadlocked statement, a pool validation
+//! check) are handed to a [`RetryStrategy`], letting applications reuse
+//! whatever backoff infrastructure they already have instead of learning a
+//! second one.
+
+use std::time::Duration;
+
+/// Decides whether a failed operation should be retried, and if so, after
+/// how long.
+///
+/// Implementations are consulted with the error that occurred and the
+/// number of attempts made so far (starting at `1` for the first failure).
+/// Returning `None` gives up; returning `Some(duration)` asks the caller to
+/// wait `duration` before trying again.
+pub trait RetryStrategy: Send + Sync {
+    /// Whether the operation should be retried, and after which delay.
+    fn should_retry(&self, error: &crate::Error, attempt: u32) -> Option<Duration>;
+}
+
+/// Never retries. The default when no strategy is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryStrategy for NoRetry {
+    fn should_retry(&self, _: &crate::Error, _: u32) -> Option<Duration> {
+        None
+    }
+}
+
+/// Retries with an exponentially increasing delay, up to a maximum number of
+/// attempts.
+#[derive(Debug, Clone, Copy)]
+pub struct ExponentialBackoff {
+    base: Duration,
+    max_attempts: u32,
+    multiplier: f64,
+}
+
+impl ExponentialBackoff {
+    /// Creates a new strategy, waiting `base` after the first failure and
+    /// multiplying the delay by `multiplier` on each subsequent attempt, up
+    /// to `max_attempts` total tries.
+    pub fn new(base: Duration, multiplier: f64, max_attempts: u32) -> Self {
+        Self {
+            base,
+            multiplier,
+            max_attempts,
+        }
+    }
+}
+
+impl RetryStrategy for ExponentialBackoff {
+    fn should_retry(&self, _: &crate::Error, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let factor = self.multiplier.powi(attempt as i32 - 1);
+        Some(self.base.mul_f64(factor))
+    }
+}
+
+/// Retries with an exponentially increasing delay, randomized ("full
+/// jitter") so that several clients backing off from the same contention
+/// point, e.g. a batch of deadlocked transactions, don't all wake up and
+/// collide again at the same instant.
+#[derive(Debug, Clone, Copy)]
+pub struct JitteredBackoff {
+    base: Duration,
+    max_attempts: u32,
+    multiplier: f64,
+}
+
+impl JitteredBackoff {
+    /// Creates a new strategy. On the `n`th attempt, the delay is chosen
+    /// uniformly at random between zero and `base * multiplier^(n - 1)`, up
+    /// to `max_attempts` total tries.
+    pub fn new(base: Duration, multiplier: f64, max_attempts: u32) -> Self {
+        Self {
+            base,
+            multiplier,
+            max_attempts,
+        }
+    }
+}
+
+impl RetryStrategy for JitteredBackoff {
+    fn should_retry(&self, _: &crate::Error, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let factor = self.multiplier.powi(attempt as i32 - 1);
+        let ceiling = self.base.mul_f64(factor);
+
+        Some(ceiling.mul_f64(unit_jitter()))
+    }
+}
+
+/// A pseudo-random value in `[0, 1)`, good enough to spread out retries
+/// without pulling in a full RNG dependency for it.
+fn unit_jitter() -> f64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let mut hasher = DefaultHasher::new();
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+
+    // Salt with the hasher's own stack address so calls landing in the same
+    // clock tick still diverge.
+    (&hasher as *const DefaultHasher as usize).hash(&mut hasher);
+
+    hasher.finish() as f64 / u64::MAX as f64
+}
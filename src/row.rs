@@ -1,18 +1,55 @@
 use crate::{
     error::Error,
-    tds::codec::{ColumnData, FixedLenType, TokenRow, TypeInfo, VarLenType},
+    tds::{
+        codec::{ColumnData, ColumnFlag, FixedLenType, TokenRow, TypeInfo, VarLenType},
+        Collation,
+    },
     FromSql,
 };
+use bytes::Bytes;
+use enumflags2::BitFlags;
 use std::{fmt::Display, sync::Arc};
 
 /// A column of data from a query.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Column {
-    pub(crate) name: String,
+    pub(crate) name: Arc<str>,
+    // Pre-lowercased once at decode time, so a case-insensitive lookup by
+    // name (the default, since SQL identifiers are case-insensitive under
+    // most collations) doesn't re-lowercase the column name on every row.
+    pub(crate) name_lower: Arc<str>,
     pub(crate) column_type: ColumnType,
+    pub(crate) flags: BitFlags<ColumnFlag>,
+    pub(crate) collation: Option<Collation>,
 }
 
 impl Column {
+    pub(crate) fn new(
+        name: impl Into<Arc<str>>,
+        column_type: ColumnType,
+        flags: BitFlags<ColumnFlag>,
+        collation: Option<Collation>,
+    ) -> Self {
+        let name = name.into();
+
+        // Interned once per column, not per row: every row in a result set
+        // shares the same `Arc<Vec<Column>>`, so this only runs once for the
+        // whole result set rather than once per row.
+        let name_lower = if name.chars().any(|c| c.is_uppercase()) {
+            Arc::from(name.to_lowercase())
+        } else {
+            name.clone()
+        };
+
+        Self {
+            name,
+            name_lower,
+            column_type,
+            flags,
+            collation,
+        }
+    }
+
     /// The name of the column.
     pub fn name(&self) -> &str {
         &self.name
@@ -22,9 +59,60 @@ impl Column {
     pub fn column_type(&self) -> ColumnType {
         self.column_type
     }
+
+    /// The raw flags the server sent for this column, e.g. whether it's
+    /// nullable, an identity, computed, or part of a hidden `FOR BROWSE`
+    /// primary key. See [`ColumnFlag`] for the full set.
+    pub fn flags(&self) -> BitFlags<ColumnFlag> {
+        self.flags
+    }
+
+    /// Whether the column can contain `NULL` values.
+    pub fn is_nullable(&self) -> bool {
+        self.flags.contains(ColumnFlag::Nullable)
+    }
+
+    /// Whether the column is an identity column.
+    pub fn is_identity(&self) -> bool {
+        self.flags.contains(ColumnFlag::Identity)
+    }
+
+    /// Whether the column is computed from an expression rather than stored.
+    pub fn is_computed(&self) -> bool {
+        self.flags.contains(ColumnFlag::Computed)
+    }
+
+    /// Whether the column is writeable.
+    pub fn is_updatable(&self) -> bool {
+        self.flags.contains(ColumnFlag::Updateable)
+    }
+
+    /// Whether the column is part of a hidden primary key added to support a
+    /// `SELECT ... FOR BROWSE` statement.
+    pub fn is_hidden(&self) -> bool {
+        self.flags.contains(ColumnFlag::Hidden)
+    }
+
+    /// Whether the column is part of the primary key for a `SELECT ... FOR
+    /// BROWSE` statement.
+    pub fn is_key(&self) -> bool {
+        self.flags.contains(ColumnFlag::Key)
+    }
+
+    /// The collation the server negotiated for this column, if it's a
+    /// character type (`char`, `varchar`, `text` and their `n`-prefixed
+    /// counterparts). `None` for a column of any other type, which has no
+    /// collation of its own.
+    ///
+    /// Check this before running a big extract from a legacy database to
+    /// see what encoding the driver will use to decode `varchar`/`char`
+    /// data in the column - see [`Collation::encoding_name`].
+    pub fn collation(&self) -> Option<Collation> {
+        self.collation
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// The type of the column.
 pub enum ColumnType {
     /// The column doesn't have a specified type.
@@ -145,6 +233,14 @@ impl From<&TypeInfo> for ColumnType {
                 VarLenType::Image => Self::Image,
                 VarLenType::NText => Self::NText,
                 VarLenType::SSVariant => Self::SSVariant,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Numeric => Self::Numericn,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Decimal => Self::Decimaln,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::VarChar => Self::BigVarChar,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Binary => Self::BigBinary,
             },
             TypeInfo::VarLenSizedPrecision { ty, .. } => match ty {
                 VarLenType::Guid => Self::Guid,
@@ -175,6 +271,14 @@ impl From<&TypeInfo> for ColumnType {
                 VarLenType::Image => Self::Image,
                 VarLenType::NText => Self::NText,
                 VarLenType::SSVariant => Self::SSVariant,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Numeric => Self::Numericn,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Decimal => Self::Decimaln,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::VarChar => Self::BigVarChar,
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Binary => Self::BigBinary,
             },
             TypeInfo::Xml { .. } => Self::Xml,
         }
@@ -232,11 +336,40 @@ impl From<&TypeInfo> for ColumnType {
 /// [`IntoIterator`]: #impl-IntoIterator
 #[derive(Debug)]
 pub struct Row {
+    // Shared, not cloned, across every row of the same result set: the
+    // COLMETADATA token is decoded once and frozen into this `Arc`, so
+    // cloning a row's metadata is a refcount bump instead of a
+    // `Vec<Column>` copy, and nothing can mutate the columns out from under
+    // a row that's still being iterated.
     pub(crate) columns: Arc<Vec<Column>>,
     pub(crate) data: TokenRow<'static>,
     pub(crate) result_index: usize,
 }
 
+/// Two rows are equal if they came from the same result set (in a
+/// multi-statement query) and have the same columns and data, letting
+/// integration tests compare an expected row against an actual one, and
+/// CDC-style tools compare two snapshots of the same row for changes.
+///
+/// Comparing `ColumnData::F32`/`F64` values follows ordinary `f32`/`f64`
+/// equality, so a row holding `NaN` is never equal to another, itself
+/// included.
+impl PartialEq for Row {
+    fn eq(&self, other: &Self) -> bool {
+        self.result_index == other.result_index
+            && self.columns == other.columns
+            && self.data == other.data
+    }
+}
+
+impl std::hash::Hash for Row {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.result_index.hash(state);
+        self.columns.hash(state);
+        self.data.hash(state);
+    }
+}
+
 pub trait QueryIdx
 where
     Self: Display,
@@ -250,9 +383,59 @@ impl QueryIdx for usize {
     }
 }
 
+/// SQL identifiers are case-insensitive under most collations, so looking
+/// up a column by `&str` matches regardless of case.
 impl QueryIdx for &str {
     fn idx(&self, row: &Row) -> Option<usize> {
-        row.columns.iter().position(|c| c.name() == *self)
+        let needle = self.to_lowercase();
+        row.columns
+            .iter()
+            .position(|c| c.name_lower.as_ref() == needle)
+    }
+}
+
+/// A column index for exact, case-sensitive name matching, for the rare
+/// case where a case-insensitive [`QueryIdx`] lookup by plain `&str` would
+/// be ambiguous, e.g. a `SELECT *` joining tables whose columns differ only
+/// by case.
+///
+/// ```
+/// # use tiberius::{CaseSensitive, Config};
+/// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+/// # use std::env;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+/// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+/// # );
+/// # let config = Config::from_ado_string(&c_str)?;
+/// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+/// # tcp.set_nodelay(true)?;
+/// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+/// let row = client
+///     .query("SELECT 1 AS Foo", &[])
+///     .await?
+///     .into_row()
+///     .await?
+///     .unwrap();
+///
+/// assert_eq!(Some(1i32), row.get(CaseSensitive("Foo")));
+/// assert!(row.try_get::<i32, _>(CaseSensitive("foo")).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaseSensitive<'a>(pub &'a str);
+
+impl<'a> Display for CaseSensitive<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+impl<'a> QueryIdx for CaseSensitive<'a> {
+    fn idx(&self, row: &Row) -> Option<usize> {
+        row.columns.iter().position(|c| c.name.as_ref() == self.0)
     }
 }
 
@@ -325,11 +508,49 @@ impl Row {
     /// # Ok(())
     /// # }
     /// ```
-    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    /// True if the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The name of the column at the given zero-based ordinal, or `None` if
+    /// it's out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiberius::Config;
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = tiberius::Client::connect(config, tcp.compat_write()).await?;
+    /// let row = client
+    ///     .query("SELECT 1 AS foo, 2 AS bar", &[])
+    ///     .await?
+    ///     .into_row()
+    ///     .await?
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some("foo"), row.column_name(0));
+    /// assert_eq!(None, row.column_name(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn column_name(&self, idx: usize) -> Option<&str> {
+        self.columns.get(idx).map(|c| c.name())
+    }
+
     /// Retrieve a column value for a given column index, which can either be
     /// the zero-indexed position or the name of the column.
     ///
@@ -393,6 +614,120 @@ impl Row {
 
         R::from_sql(data)
     }
+
+    /// Like [`get`], but trims trailing space padding from a `char`/`nchar`
+    /// column, e.g. a `char(10)` value of `'abc'` comes back over the wire as
+    /// `"abc       "`; `get_trimmed` returns `"abc"`. `varchar`/`nvarchar`
+    /// columns are unaffected, since the server doesn't pad them.
+    ///
+    /// Only a trailing run of `U+0020` space characters is trimmed; other
+    /// whitespace, and any space that isn't at the end of the value, is left
+    /// alone. Use [`get`] instead if the padding itself is significant.
+    ///
+    /// # Panics
+    ///
+    /// - The column's value is not a string.
+    /// - The given index is out of bounds (column does not exist).
+    ///
+    /// [`get`]: #method.get
+    #[track_caller]
+    pub fn get_trimmed<I>(&self, idx: I) -> Option<&str>
+    where
+        I: QueryIdx,
+    {
+        let padded = idx
+            .idx(self)
+            .and_then(|i| self.columns.get(i))
+            .map_or(false, |c| {
+                matches!(c.column_type, ColumnType::BigChar | ColumnType::NChar)
+            });
+        let value = self.get::<&str, I>(idx);
+
+        if padded {
+            value.map(|s| s.trim_end_matches(' '))
+        } else {
+            value
+        }
+    }
+
+    /// Returns the raw, undecoded bytes of a value, if the column's wire
+    /// representation was left as a byte blob rather than being decoded
+    /// into one of the crate's own types.
+    ///
+    /// Such values are the ones decoded as binary data, e.g. those of
+    /// `varbinary` columns. `UDT` values are not available here: a result
+    /// set with a `UDT` column fails with [`Error::Unsupported`] while its
+    /// column metadata is decoded.
+    ///
+    /// [`Error::Unsupported`]: crate::Error::Unsupported
+    ///
+    /// Returns `None` if the index is out of bounds, the value is `NULL`,
+    /// or the column was already decoded into a typed value.
+    pub fn get_raw<I>(&self, idx: I) -> Option<&[u8]>
+    where
+        I: QueryIdx,
+    {
+        let idx = idx.idx(self)?;
+
+        match self.data.get(idx)? {
+            ColumnData::Binary(Some(bytes)) => Some(bytes.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A single row's exact `ROW`/`NBCROW` wire bytes, produced by
+/// [`Client::raw_query`] instead of a decoded [`Row`] - the columns are
+/// still described by the accompanying [`ResultMetadata`], but the values
+/// themselves are left undecoded for the caller to interpret, skipping the
+/// per-column `ColumnData` conversion entirely.
+///
+/// [`Client::raw_query`]: crate::Client::raw_query
+/// [`ResultMetadata`]: crate::ResultMetadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRow {
+    pub(crate) columns: Arc<Vec<Column>>,
+    pub(crate) data: Bytes,
+    pub(crate) result_index: usize,
+}
+
+impl RawRow {
+    /// The columns present in this row, in the same order as the values in
+    /// [`data`].
+    ///
+    /// [`data`]: RawRow::data
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// The result set number, starting from zero and increasing if the stream
+    /// has results from more than one query.
+    pub fn result_index(&self) -> usize {
+        self.result_index
+    }
+
+    /// Returns the number of columns in the row.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// True if the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The name of the column at the given zero-based ordinal, or `None` if
+    /// it's out of bounds.
+    pub fn column_name(&self, idx: usize) -> Option<&str> {
+        self.columns.get(idx).map(|c| c.name())
+    }
+
+    /// The row's exact `ROW`/`NBCROW` token bytes, as sent by the server,
+    /// without the leading token-type byte. Whether they are a `ROW` or an
+    /// `NBCROW` body (which starts with the null bitmap) is not recorded.
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
 }
 
 impl IntoIterator for Row {
@@ -0,0 +1,205 @@
+//! Convenience wrapper around the catalog views SQL Server's own `sp_help`
+//! queries, assembling a table's columns, indexes and key constraints into
+//! typed structs so schema-diff and migration tooling built on this crate
+//! doesn't have to hand-write the `sys.columns`/`sys.indexes` joins itself.
+//!
+//! No live SQL Server was available while writing this to check the
+//! decoded columns against a running instance, so treat the exact query
+//! shape as a best effort against Microsoft's documented catalog views.
+
+use crate::{Error, Row};
+use std::collections::BTreeMap;
+
+/// One column of a table, as described by [`Client::describe_table`].
+///
+/// [`Client::describe_table`]: crate::Client::describe_table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumn {
+    name: String,
+    sql_type: String,
+    max_length: i16,
+    is_nullable: bool,
+    is_identity: bool,
+    default_definition: Option<String>,
+}
+
+impl TableColumn {
+    /// The column's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The column's type name, e.g. `varchar` or `int`, as found in
+    /// `sys.types`; for a column of an alias type this is the alias's own
+    /// name, not its base type.
+    pub fn sql_type(&self) -> &str {
+        &self.sql_type
+    }
+
+    /// The column's maximum length in bytes as stored in `sys.columns`, or
+    /// `-1` for a `(max)` type.
+    pub fn max_length(&self) -> i16 {
+        self.max_length
+    }
+
+    /// Whether the column allows `NULL`.
+    pub fn is_nullable(&self) -> bool {
+        self.is_nullable
+    }
+
+    /// Whether the column is an `IDENTITY` column.
+    pub fn is_identity(&self) -> bool {
+        self.is_identity
+    }
+
+    /// The column's `DEFAULT` constraint definition, e.g. `((0))`, if it has
+    /// one.
+    pub fn default_definition(&self) -> Option<&str> {
+        self.default_definition.as_deref()
+    }
+}
+
+/// One index of a table, as described by [`Client::describe_table`].
+///
+/// [`Client::describe_table`]: crate::Client::describe_table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableIndex {
+    name: String,
+    is_primary_key: bool,
+    is_unique: bool,
+    columns: Vec<String>,
+}
+
+impl TableIndex {
+    /// The index's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the index backs the table's primary key.
+    pub fn is_primary_key(&self) -> bool {
+        self.is_primary_key
+    }
+
+    /// Whether the index enforces uniqueness.
+    pub fn is_unique(&self) -> bool {
+        self.is_unique
+    }
+
+    /// The indexed columns, in key order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+/// A table's columns and indexes, as returned by [`Client::describe_table`].
+///
+/// [`Client::describe_table`]: crate::Client::describe_table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDescription {
+    columns: Vec<TableColumn>,
+    indexes: Vec<TableIndex>,
+}
+
+impl TableDescription {
+    /// The table's columns, in column order.
+    pub fn columns(&self) -> &[TableColumn] {
+        &self.columns
+    }
+
+    /// The table's indexes, including the one backing its primary key, if
+    /// any.
+    pub fn indexes(&self) -> &[TableIndex] {
+        &self.indexes
+    }
+}
+
+fn required<'a, T: crate::FromSql<'a>>(row: &'a Row, column: &'static str) -> crate::Result<T> {
+    row.try_get(column)?.ok_or_else(|| {
+        Error::Protocol(format!("catalog view result missing column `{}`", column).into())
+    })
+}
+
+pub(crate) const COLUMNS_SQL: &str = "
+    SELECT
+        c.name AS column_name,
+        ty.name AS sql_type,
+        c.max_length,
+        c.is_nullable,
+        c.is_identity,
+        dc.definition AS default_definition
+    FROM sys.columns c
+    JOIN sys.types ty ON ty.user_type_id = c.user_type_id
+    LEFT JOIN sys.default_constraints dc
+        ON dc.parent_object_id = c.object_id AND dc.parent_column_id = c.column_id
+    WHERE c.object_id = OBJECT_ID(@P1)
+    ORDER BY c.column_id";
+
+pub(crate) const INDEXES_SQL: &str = "
+    SELECT
+        i.name AS index_name,
+        i.is_primary_key,
+        i.is_unique,
+        c.name AS column_name
+    FROM sys.indexes i
+    JOIN sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+    JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+    WHERE i.object_id = OBJECT_ID(@P1) AND i.name IS NOT NULL
+    ORDER BY i.index_id, ic.key_ordinal";
+
+pub(crate) fn parse_column(row: &Row) -> crate::Result<TableColumn> {
+    Ok(TableColumn {
+        name: required::<&str>(row, "column_name")?.to_owned(),
+        sql_type: required::<&str>(row, "sql_type")?.to_owned(),
+        max_length: required(row, "max_length")?,
+        is_nullable: required(row, "is_nullable")?,
+        is_identity: required(row, "is_identity")?,
+        default_definition: row
+            .try_get::<&str, _>("default_definition")?
+            .map(str::to_owned),
+    })
+}
+
+/// Groups the flattened `index_name`/`column_name` rows [`INDEXES_SQL`]
+/// returns into one [`TableIndex`] per index, preserving key column order.
+pub(crate) fn parse_indexes(rows: &[Row]) -> crate::Result<Vec<TableIndex>> {
+    let mut order = Vec::new();
+    let mut by_name: BTreeMap<&str, TableIndex> = BTreeMap::new();
+
+    for row in rows {
+        let name = required::<&str>(row, "index_name")?;
+        let column = required::<&str>(row, "column_name")?.to_owned();
+
+        by_name
+            .entry(name)
+            .or_insert_with(|| {
+                order.push(name);
+                TableIndex {
+                    name: name.to_owned(),
+                    is_primary_key: false,
+                    is_unique: false,
+                    columns: Vec::new(),
+                }
+            })
+            .columns
+            .push(column);
+    }
+
+    for row in rows {
+        let name = required::<&str>(row, "index_name")?;
+
+        if let Some(index) = by_name.get_mut(name) {
+            index.is_primary_key = required(row, "is_primary_key")?;
+            index.is_unique = required(row, "is_unique")?;
+        }
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|name| by_name.remove(name))
+        .collect())
+}
+
+pub(crate) fn assemble(columns: Vec<TableColumn>, indexes: Vec<TableIndex>) -> TableDescription {
+    TableDescription { columns, indexes }
+}
@@ -0,0 +1,75 @@
+//! Convenience wrapper around `RECEIVE` for draining a Service Broker
+//! queue, so queue-based integrations don't have to hand-write the
+//! `WAITFOR (RECEIVE ...)` statement and the resulting column layout
+//! themselves.
+//!
+//! No live SQL Server Service Broker setup was available while writing
+//! this to check the decoded columns against a running instance, so treat
+//! the exact column list as a best effort against Microsoft's documented
+//! `RECEIVE` output.
+
+use crate::{Error, Row};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// A single message returned by `RECEIVE` from a Service Broker queue.
+///
+/// [`RECEIVE`]: https://learn.microsoft.com/en-us/sql/t-sql/statements/receive-transact-sql
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceBrokerMessage {
+    conversation_handle: Uuid,
+    message_type_name: String,
+    service_name: String,
+    body: Option<Vec<u8>>,
+}
+
+impl ServiceBrokerMessage {
+    /// The conversation this message belongs to. Pass this to `END
+    /// CONVERSATION` or a reply `SEND` on the same conversation.
+    pub fn conversation_handle(&self) -> Uuid {
+        self.conversation_handle
+    }
+
+    /// The name of the message type, e.g. one of the queue's contract's
+    /// message types, or one of the built-in
+    /// `http://schemas.microsoft.com/SQL/ServiceBroker/...` system types
+    /// sent when a conversation ends or errors.
+    pub fn message_type_name(&self) -> &str {
+        &self.message_type_name
+    }
+
+    /// The name of the service the message was sent to.
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// The message body, or `None` for message types that carry no body,
+    /// e.g. `EndDialog`.
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+}
+
+fn required<'a, T: crate::FromSql<'a>>(row: &'a Row, column: &'static str) -> crate::Result<T> {
+    row.try_get(column)?.ok_or_else(|| {
+        Error::Protocol(format!("RECEIVE result missing column `{}`", column).into())
+    })
+}
+
+pub(crate) fn parse_message(row: &Row) -> crate::Result<ServiceBrokerMessage> {
+    Ok(ServiceBrokerMessage {
+        conversation_handle: required(row, "conversation_handle")?,
+        message_type_name: required::<&str>(row, "message_type_name")?.to_owned(),
+        service_name: required::<&str>(row, "service_name")?.to_owned(),
+        body: row.try_get::<&[u8], _>("message_body")?.map(<[u8]>::to_vec),
+    })
+}
+
+pub(crate) fn build_receive_sql(queue: &str, top: u32, timeout: Duration) -> String {
+    format!(
+        "WAITFOR (RECEIVE TOP({}) conversation_handle, message_type_name, service_name, message_body FROM {}), TIMEOUT {}",
+        top,
+        queue,
+        timeout.as_millis(),
+    )
+}
@@ -92,6 +92,54 @@ macro_rules! varchar_reader {
     };
 }
 
+pin_project! {
+    /// Reads `len` bytes into an owned buffer in as few underlying
+    /// `poll_read` calls as possible, instead of the byte-at-a-time loop a
+    /// caller would otherwise need to write using [`SqlReadBytes::read_u8`].
+    #[doc(hidden)]
+    pub struct ReadBytes<R> {
+        #[pin]
+        src: R,
+        buf: Vec<u8>,
+        read: usize,
+    }
+}
+
+#[allow(dead_code)]
+impl<R> ReadBytes<R> {
+    pub(crate) fn new(src: R, len: usize) -> Self {
+        Self {
+            src,
+            buf: vec![0; len],
+            read: 0,
+        }
+    }
+}
+
+impl<R> Future for ReadBytes<R>
+where
+    R: AsyncRead,
+{
+    type Output = io::Result<Vec<u8>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut me = self.project();
+
+        while *me.read < me.buf.len() {
+            let n = match me.src.as_mut().poll_read(cx, &mut me.buf[*me.read..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(UnexpectedEof.into())),
+                Poll::Ready(Ok(n)) => n,
+            };
+
+            *me.read += n;
+        }
+
+        Poll::Ready(Ok(std::mem::take(me.buf)))
+    }
+}
+
 macro_rules! bytes_reader {
     ($name:ident, $ty:ty, $reader:ident) => {
         bytes_reader!($name, $ty, $reader, size_of::<$ty>());
@@ -299,6 +347,15 @@ pub(crate) trait SqlReadBytes: AsyncRead + Unpin {
     {
         ReadUSVarchar::new(self)
     }
+
+    // Read `len` bytes, e.g. a string or binary column value, in one go
+    // instead of one byte at a time.
+    fn read_bytes(&mut self, len: usize) -> ReadBytes<&mut Self>
+    where
+        Self: Unpin,
+    {
+        ReadBytes::new(self, len)
+    }
 }
 
 varchar_reader!(ReadBVarchar, ReadU8);
@@ -1,11 +1,14 @@
 pub mod codec;
-mod collation;
+pub mod collation;
 mod context;
 pub mod numeric;
+mod server_kind;
 pub mod stream;
 pub mod time;
 pub mod xml;
 
+pub use server_kind::ServerKind;
+
 pub(crate) use collation::*;
 pub(crate) use context::*;
 pub(crate) use numeric::*;
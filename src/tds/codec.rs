@@ -1,6 +1,6 @@
 mod batch_request;
 mod bulk_load;
-mod column_data;
+pub(crate) mod column_data;
 mod decode;
 mod encode;
 mod guid;
@@ -41,6 +41,7 @@ enum AllHeaderTy {
     TraceActivity = 3,
 }
 
+#[derive(Debug)]
 pub struct PacketCodec;
 
 pub(crate) async fn collect_from<S, T>(stream: &mut S) -> crate::Result<T>
@@ -13,9 +13,11 @@ mod float;
 mod guid;
 mod image;
 mod int;
+#[cfg(feature = "legacy-types")]
+mod legacy;
 mod money;
 mod plp;
-mod string;
+pub(crate) mod string;
 mod text;
 #[cfg(feature = "tds73")]
 mod time;
@@ -37,8 +39,35 @@ use uuid::Uuid;
 
 const MAX_NVARCHAR_SIZE: usize = 1 << 30;
 
+/// Above this many UTF-8 bytes, a `String` parameter without a known
+/// column type switches from a fixed `nvarchar(4000)` to `nvarchar(max)`
+/// encoding so the server never has to reject it for being too long. A
+/// string's UTF-8 length is never smaller than its UTF-16 code unit count,
+/// so every string sent in the short form fits in 4000 code units.
+const NVARCHAR_SHORT_LIMIT: usize = 4000;
+
+/// Above this many bytes, a `Vec<u8>` parameter without a known column type
+/// switches from a fixed `varbinary(8000)` to `varbinary(max)` encoding for
+/// the same reason as [`NVARCHAR_SHORT_LIMIT`].
+const VARBINARY_SHORT_LIMIT: usize = 8000;
+
 #[derive(Clone, Debug, PartialEq)]
 /// A container of a value that can be represented as a TDS value.
+///
+/// The `'a` lifetime is real on the way out: an outbound parameter built
+/// through [`IntoSql`]/[`ToSql`] can borrow `str`/`[u8]` data straight from
+/// the caller instead of cloning it into an owned buffer. It is currently
+/// vestigial on the way in: every `ColumnData` produced by decoding a row
+/// off the wire (see `column_data::*::decode`) is `ColumnData<'static>`,
+/// because decoding a `String`/`Vec<u8>` value allocates it directly rather
+/// than borrowing from a retained packet buffer. Making row values truly
+/// zero-copy would mean threading a shared (e.g. `Arc`-backed) packet buffer
+/// through the whole read path so a `Cow::Borrowed` could point back into
+/// it — infrastructure this crate doesn't have yet; see the module doc on
+/// `benches/bench.rs` for the same gap from the benchmarking side.
+///
+/// [`IntoSql`]: crate::IntoSql
+/// [`ToSql`]: crate::ToSql
 pub enum ColumnData<'a> {
     /// 8-bit integer, unsigned.
     U8(Option<u8>),
@@ -86,6 +115,46 @@ pub enum ColumnData<'a> {
     DateTimeOffset(Option<DateTimeOffset>),
 }
 
+impl<'a> std::hash::Hash for ColumnData<'a> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            ColumnData::U8(v) => v.hash(state),
+            ColumnData::I16(v) => v.hash(state),
+            ColumnData::I32(v) => v.hash(state),
+            ColumnData::I64(v) => v.hash(state),
+            // `f32`/`f64` don't implement `Hash`: NaN != NaN is harmless for
+            // the a == b => hash(a) == hash(b) rule (unequal values can
+            // hash however they like), but 0.0 == -0.0 isn't, since they
+            // have different bit patterns. Canonicalize -0.0 to 0.0 before
+            // hashing to keep the rule intact.
+            ColumnData::F32(v) => v
+                .map(|v| if v == 0.0 { 0.0 } else { v }.to_bits())
+                .hash(state),
+            ColumnData::F64(v) => v
+                .map(|v| if v == 0.0 { 0.0 } else { v }.to_bits())
+                .hash(state),
+            ColumnData::Bit(v) => v.hash(state),
+            ColumnData::String(v) => v.hash(state),
+            ColumnData::Guid(v) => v.hash(state),
+            ColumnData::Binary(v) => v.hash(state),
+            ColumnData::Numeric(v) => v.hash(state),
+            ColumnData::Xml(v) => v.hash(state),
+            ColumnData::DateTime(v) => v.hash(state),
+            ColumnData::SmallDateTime(v) => v.hash(state),
+            #[cfg(feature = "tds73")]
+            ColumnData::Time(v) => v.hash(state),
+            #[cfg(feature = "tds73")]
+            ColumnData::Date(v) => v.hash(state),
+            #[cfg(feature = "tds73")]
+            ColumnData::DateTime2(v) => v.hash(state),
+            #[cfg(feature = "tds73")]
+            ColumnData::DateTimeOffset(v) => v.hash(state),
+        }
+    }
+}
+
 impl<'a> ColumnData<'a> {
     pub(crate) fn type_name(&self) -> Cow<'static, str> {
         match self {
@@ -123,6 +192,16 @@ impl<'a> ColumnData<'a> {
         }
     }
 
+    /// Dispatches to the decoder for `ctx`'s wire type. `ctx` itself is
+    /// parsed once per column from `COLMETADATA` and reused unchanged for
+    /// every row, so this match only ever re-derives *which* decoder to
+    /// call, never anything about the column's type. A fieldless enum match
+    /// like this already lowers to a single indirect jump, the same cost a
+    /// pre-resolved table of decoder closures would have — with the added
+    /// downside that decoders here are `async fn`s, so a closure table would
+    /// need to box each future, trading a jump table for an allocation on
+    /// every cell. Keeping the match is the faster option, not just the
+    /// simpler one.
     pub(crate) async fn decode<R>(src: &mut R, ctx: &TypeInfo) -> crate::Result<ColumnData<'a>>
     where
         R: SqlReadBytes + Unpin,
@@ -134,6 +213,10 @@ impl<'a> ColumnData<'a> {
                 VarLenType::Decimaln | VarLenType::Numericn => {
                     ColumnData::Numeric(Numeric::decode(src, *scale).await?)
                 }
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Decimal | VarLenType::Numeric => {
+                    ColumnData::Numeric(Numeric::decode(src, *scale).await?)
+                }
                 _ => todo!(),
             },
             TypeInfo::Xml { schema, size } => xml::decode(src, *size, schema.clone()).await?,
@@ -416,9 +499,9 @@ impl<'a> Encode<BytesMutWithTypeInfo<'a>> for ColumnData<'a> {
                     }
                 }
             }
-            (ColumnData::String(Some(ref s)), None) if s.len() <= 4000 => {
+            (ColumnData::String(Some(ref s)), None) if s.len() <= NVARCHAR_SHORT_LIMIT => {
                 dst.put_u8(VarLenType::NVarchar as u8);
-                dst.put_u16_le(8000);
+                dst.put_u16_le((NVARCHAR_SHORT_LIMIT * 2) as u16);
                 dst.extend_from_slice(&[0u8; 5][..]);
 
                 let mut length = 0u16;
@@ -503,9 +586,9 @@ impl<'a> Encode<BytesMutWithTypeInfo<'a>> for ColumnData<'a> {
                     }
                 }
             }
-            (ColumnData::Binary(Some(bytes)), None) if bytes.len() <= 8000 => {
+            (ColumnData::Binary(Some(bytes)), None) if bytes.len() <= VARBINARY_SHORT_LIMIT => {
                 dst.put_u8(VarLenType::BigVarBin as u8);
-                dst.put_u16_le(8000);
+                dst.put_u16_le(VARBINARY_SHORT_LIMIT as u16);
                 dst.put_u16_le(bytes.len() as u16);
                 dst.extend(bytes.into_owned());
             }
@@ -708,6 +791,30 @@ mod tests {
         assert_eq!(nd, d)
     }
 
+    // Same as `test_round_trip`, but without a `TypeInfo` supplied up front,
+    // exercising the `(ColumnData::_, None)` encode arms that pick a default
+    // wire type themselves - the path an RPC parameter takes when nothing
+    // overrides its `type_info` (e.g. `Query::bind`).
+    async fn test_none_context_round_trip<'a>(d: ColumnData<'a>) {
+        let mut buf = BytesMut::new();
+        let mut buf_with_ti = BytesMutWithTypeInfo::new(&mut buf);
+
+        d.clone()
+            .encode(&mut buf_with_ti)
+            .expect("encode must succeed");
+
+        let mut reader = buf.into_sql_read_bytes();
+        let ti = TypeInfo::decode(&mut reader)
+            .await
+            .expect("type info decode must succeed");
+
+        let nd = ColumnData::decode(&mut reader, &ti)
+            .await
+            .expect("decode must succeed");
+
+        assert_eq!(nd, d)
+    }
+
     #[tokio::test]
     async fn i32_with_varlen_int() {
         test_round_trip(
@@ -1047,6 +1154,23 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn string_with_varlen_nvarchar_surrogate_pair() {
+        // "\u{1F600}" (grinning face) sits outside the basic multilingual
+        // plane, so it needs a UTF-16 surrogate pair (two code units, four
+        // bytes) rather than a single code unit like the rest of the ASCII
+        // test strings above.
+        test_round_trip(
+            TypeInfo::VarLenSized(VarLenContext::new(
+                VarLenType::NVarchar,
+                40,
+                Some(Collation::new(13632521, 52)),
+            )),
+            ColumnData::String(Some("a\u{1F600}b".into())),
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn string_with_varlen_nchar() {
         test_round_trip(
@@ -1173,6 +1297,11 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn datetime_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::DateTime(Some(DateTime::new(200, 3000)))).await;
+    }
+
     #[tokio::test]
     async fn datetime_with_fixedlen_datetime() {
         test_round_trip(
@@ -1200,6 +1329,14 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn smalldatetime_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::SmallDateTime(Some(SmallDateTime::new(
+            200, 3000,
+        ))))
+        .await;
+    }
+
     #[tokio::test]
     async fn smalldatetime_with_fixedlen_datetime4() {
         test_round_trip(
@@ -1229,6 +1366,12 @@ mod tests {
         .await;
     }
 
+    #[cfg(feature = "tds73")]
+    #[tokio::test]
+    async fn date_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::Date(Some(Date::new(200)))).await;
+    }
+
     #[cfg(feature = "tds73")]
     #[tokio::test]
     async fn time_with_varlen_timen() {
@@ -1249,6 +1392,12 @@ mod tests {
         .await;
     }
 
+    #[cfg(feature = "tds73")]
+    #[tokio::test]
+    async fn time_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::Time(Some(Time::new(55, 7)))).await;
+    }
+
     #[cfg(feature = "tds73")]
     #[tokio::test]
     async fn datetime2_with_varlen_datetime2() {
@@ -1269,6 +1418,16 @@ mod tests {
         .await;
     }
 
+    #[cfg(feature = "tds73")]
+    #[tokio::test]
+    async fn datetime2_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::DateTime2(Some(DateTime2::new(
+            Date::new(55),
+            Time::new(222, 7),
+        ))))
+        .await;
+    }
+
     #[cfg(feature = "tds73")]
     #[tokio::test]
     async fn datetimeoffset_with_varlen_datetimeoffsetn() {
@@ -1292,6 +1451,16 @@ mod tests {
         .await;
     }
 
+    #[cfg(feature = "tds73")]
+    #[tokio::test]
+    async fn datetimeoffset_with_no_type_context() {
+        test_none_context_round_trip(ColumnData::DateTimeOffset(Some(DateTimeOffset::new(
+            DateTime2::new(Date::new(55), Time::new(222, 7)),
+            -8,
+        ))))
+        .await;
+    }
+
     #[cfg(feature = "tds73")]
     #[tokio::test]
     async fn xml_with_xml() {
@@ -1347,4 +1516,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn binary_into_sql_borrows_instead_of_copying() {
+        use crate::IntoSql;
+
+        let bytes = b"hello".to_vec();
+
+        match bytes.as_slice().into_sql() {
+            ColumnData::Binary(Some(Cow::Borrowed(b))) => assert_eq!(b, bytes.as_slice()),
+            other => panic!("expected a borrowed Cow, got {:?}", other),
+        }
+    }
 }
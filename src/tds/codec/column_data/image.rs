@@ -1,5 +1,10 @@
 use crate::{sql_read_bytes::SqlReadBytes, ColumnData};
 
+/// `IMAGE` predates PLP: a TEXTPTR (a length byte, `0` meaning `NULL`,
+/// followed by that many now-unused pointer bytes), an 8-byte timestamp,
+/// then a plain `u32` length and the data itself - not the chunked,
+/// unknown-length-capable encoding `varbinary(max)` uses (see
+/// `column_data::plp`).
 pub(crate) async fn decode<R>(src: &mut R) -> crate::Result<ColumnData<'static>>
 where
     R: SqlReadBytes + Unpin,
@@ -0,0 +1,19 @@
+use crate::sql_read_bytes::SqlReadBytes;
+
+/// Decodes a single value of a pre-7.2 `VARCHAR`/`BINARY` column: a one-byte
+/// length followed by that many raw bytes, with a length of `0` meaning
+/// `NULL`. Unlike `BIGVARCHAR`/`BIGBINARY`, these predate TDS 7.x's
+/// partially length-prefixed (PLP) encoding, so there's no per-value
+/// "unknown length" marker to handle.
+pub(crate) async fn decode_bytes<R>(src: &mut R) -> crate::Result<Option<Vec<u8>>>
+where
+    R: SqlReadBytes + Unpin,
+{
+    let len = src.read_u8().await? as usize;
+
+    if len == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(src.read_bytes(len).await?))
+    }
+}
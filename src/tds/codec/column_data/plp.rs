@@ -13,15 +13,7 @@ where
             match len {
                 // NULL
                 0xffff => Ok(None),
-                _ => {
-                    let mut data = Vec::with_capacity(len as usize);
-
-                    for _ in 0..len {
-                        data.push(src.read_u8().await?);
-                    }
-
-                    Ok(Some(data))
-                }
+                _ => Ok(Some(src.read_bytes(len as usize).await?)),
             }
         }
         // Unknown size, length-prefixed blobs
@@ -37,25 +29,14 @@ where
                 _ => Vec::with_capacity(len as usize),
             };
 
-            let mut chunk_data_left = 0;
-
             loop {
-                if chunk_data_left == 0 {
-                    // We have no chunk. Start a new one.
-                    let chunk_size = src.read_u32_le().await? as usize;
-
-                    if chunk_size == 0 {
-                        break; // found a sentinel, we're done
-                    } else {
-                        chunk_data_left = chunk_size
-                    }
-                } else {
-                    // Just read a byte
-                    let byte = src.read_u8().await?;
-                    chunk_data_left -= 1;
+                let chunk_size = src.read_u32_le().await? as usize;
 
-                    data.push(byte);
+                if chunk_size == 0 {
+                    break; // found a sentinel, we're done
                 }
+
+                data.extend(src.read_bytes(chunk_size).await?);
             }
 
             Ok(Some(data))
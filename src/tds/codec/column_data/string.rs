@@ -1,10 +1,36 @@
 use std::borrow::Cow;
 
-use byteorder::{ByteOrder, LittleEndian};
 use encoding::DecoderTrap;
 
 use crate::{error::Error, sql_read_bytes::SqlReadBytes, tds::Collation, VarLenType};
 
+/// Decodes a UTF-16LE byte buffer straight into a `String`, without the
+/// intermediate `Vec<u16>` a `buf.chunks(2).map(..).collect()` followed by
+/// `String::from_utf16` would allocate - `nvarchar`/`nchar` columns are the
+/// hottest decode path in a typical row, so the extra pass and allocation
+/// are worth avoiding here.
+///
+/// `buf.len()` must be even; callers already validate this since an odd
+/// length means a malformed value.
+///
+/// `pub` (rather than private) only so it can be re-exported, hidden from
+/// the docs, as [`crate::__bench_decode_utf16le`] for `benches/bench.rs`; the
+/// module tree it lives in stays `pub(crate)`, so this isn't reachable except
+/// through that one re-export.
+pub fn decode_utf16le(buf: &[u8]) -> crate::Result<String> {
+    let units = buf
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+
+    let mut s = String::with_capacity(buf.len() / 2);
+
+    for unit in char::decode_utf16(units) {
+        s.push(unit.map_err(|_| Error::Utf16)?);
+    }
+
+    Ok(s)
+}
+
 pub(crate) async fn decode<R>(
     src: &mut R,
     ty: VarLenType,
@@ -36,8 +62,7 @@ where
                 return Err(Error::Protocol("nvarchar: invalid plp length".into()));
             }
 
-            let buf: Vec<_> = buf.chunks(2).map(LittleEndian::read_u16).collect();
-            Ok(Some(String::from_utf16(&buf)?.into()))
+            Ok(Some(decode_utf16le(&buf)?.into()))
         }
         _ => Ok(None),
     }
@@ -2,6 +2,11 @@ use encoding::DecoderTrap;
 
 use crate::{error::Error, sql_read_bytes::SqlReadBytes, tds::Collation, ColumnData};
 
+/// `TEXT`/`NTEXT` predate PLP: each value is a TEXTPTR (a length byte, `0`
+/// meaning `NULL`, followed by that many now-unused pointer bytes), an
+/// 8-byte timestamp, then a plain `u32` length and the data itself - not
+/// the chunked, unknown-length-capable encoding `varchar(max)`/
+/// `nvarchar(max)` use (see `column_data::plp`).
 pub(crate) async fn decode<R>(
     src: &mut R,
     collation: Option<Collation>,
@@ -21,6 +21,28 @@ where
         BigChar | BigVarChar | NChar | NVarchar => {
             ColumnData::String(super::string::decode(src, ty, len, collation).await?)
         }
+        // Pre-7.2 `VARCHAR`/`BINARY` predate the PLP-style per-value length
+        // used by their `BIGVARCHAR`/`BIGBINARY` replacements: each value is
+        // a single length byte (`0` meaning `NULL`) followed by that many
+        // raw bytes, with no PLP unknown-length marker.
+        #[cfg(feature = "legacy-types")]
+        VarChar => {
+            let collation = collation.as_ref().unwrap();
+            let encoder = collation.encoding()?;
+
+            match super::legacy::decode_bytes(src).await? {
+                Some(buf) => {
+                    let s = encoder
+                        .decode(buf.as_ref(), encoding::DecoderTrap::Strict)
+                        .map_err(crate::Error::Encoding)?;
+
+                    ColumnData::String(Some(s.into()))
+                }
+                None => ColumnData::String(None),
+            }
+        }
+        #[cfg(feature = "legacy-types")]
+        Binary => ColumnData::Binary(super::legacy::decode_bytes(src).await?.map(Into::into)),
         Money => {
             let len = src.read_u8().await?;
             super::money::decode(src, len).await?
@@ -38,6 +60,11 @@ where
         #[cfg(feature = "tds73")]
         DatetimeOffsetn => super::datetimeoffsetn::decode(src, len as usize).await?,
         BigBinary | BigVarBin => super::binary::decode(src, len).await?,
+        // UDTs are PLP-encoded like `varbinary(max)`, so a `Udt` context
+        // decodes to raw bytes. Rows from a server do not reach this arm:
+        // `TypeInfo::decode` rejects UDT column metadata with
+        // `Error::Unsupported`.
+        Udt => super::binary::decode(src, len).await?,
         Text => super::text::decode(src, collation).await?,
         NText => super::text::decode(src, None).await?,
         Image => super::image::decode(src).await?,
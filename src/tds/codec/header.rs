@@ -13,6 +13,10 @@ uint_enum! {
         Rpc = 3,
         TabularResult = 4,
         AttentionSignal = 6,
+        /// Carries the `COLMETADATA`/`ROW`/`DONE` tokens a
+        /// [`BulkLoadRequest`] writes after an `INSERT BULK` statement.
+        ///
+        /// [`BulkLoadRequest`]: crate::BulkLoadRequest
         BulkLoad = 7,
         /// Federated Authentication Token
         Fat = 8,
@@ -108,6 +112,14 @@ impl PacketHeader {
         }
     }
 
+    pub fn attention(id: u8) -> Self {
+        Self {
+            ty: PacketType::AttentionSignal,
+            status: PacketStatus::EndOfMessage,
+            ..Self::new(0, id)
+        }
+    }
+
     pub fn set_status(&mut self, status: PacketStatus) {
         self.status = status;
     }
@@ -127,6 +139,12 @@ impl PacketHeader {
     pub fn length(&self) -> u16 {
         self.length
     }
+
+    /// The process ID on the server that produced this packet, for
+    /// debugging and monitoring purposes.
+    pub fn spid(&self) -> u16 {
+        self.spid
+    }
 }
 
 impl<B> Encode<B> for PacketHeader
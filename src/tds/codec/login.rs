@@ -28,6 +28,8 @@ impl Default for FeatureLevel {
 }
 
 impl FeatureLevel {
+    /// The width, in bytes, of the row count field in a `DONE`/`DONEPROC`/
+    /// `DONEINPROC` token: 4 bytes before TDS 7.2 (SQL Server 2005), 8 after.
     pub fn done_row_count_bytes(self) -> u8 {
         if self as u8 >= FeatureLevel::SqlServer2005 as u8 {
             8
@@ -172,6 +174,8 @@ pub struct LoginMessage<'a> {
     server_name: Cow<'a, str>,
     /// the default database to connect to
     db_name: Cow<'a, str>,
+    /// requested via [`OptionFlag3::RequestChangePassword`]
+    new_password: Cow<'a, str>,
     fed_auth_ext: Option<FedAuthExt<'a>>,
 }
 
@@ -202,6 +206,10 @@ impl<'a> LoginMessage<'a> {
         self.app_name = name.into();
     }
 
+    pub fn packet_size(&mut self, packet_size: u32) {
+        self.packet_size = packet_size;
+    }
+
     pub fn db_name(&mut self, db_name: impl Into<Cow<'a, str>>) {
         self.db_name = db_name.into();
     }
@@ -210,6 +218,16 @@ impl<'a> LoginMessage<'a> {
         self.server_name = server_name.into();
     }
 
+    /// Marks the connection's application intent as read-only, see
+    /// [`LoginTypeFlag::ReadOnlyIntent`].
+    pub fn readonly_intent(&mut self, enable: bool) {
+        if enable {
+            self.type_flags.insert(LoginTypeFlag::ReadOnlyIntent);
+        } else {
+            self.type_flags.remove(LoginTypeFlag::ReadOnlyIntent);
+        }
+    }
+
     pub fn user_name(&mut self, user_name: impl Into<Cow<'a, str>>) {
         self.username = user_name.into();
     }
@@ -218,6 +236,16 @@ impl<'a> LoginMessage<'a> {
         self.password = password.into();
     }
 
+    /// Requests that the server change this login's password to
+    /// `new_password` as part of this same `LOGIN7` exchange
+    /// ([`OptionFlag3::RequestChangePassword`]), so a login whose password
+    /// has expired can rotate it and still succeed in one round trip.
+    pub fn change_password(&mut self, new_password: impl Into<Cow<'a, str>>) {
+        self.option_flags_3
+            .insert(OptionFlag3::RequestChangePassword);
+        self.new_password = new_password.into();
+    }
+
     pub fn aad_token(
         &mut self,
         token: impl Into<Cow<'a, str>>,
@@ -269,7 +297,7 @@ impl<'a> Encode<BytesMut> for LoginMessage<'a> {
             &"".into(), // 9. ClientId (6 bytes); this is included in var_data so we don't lack the bytes of cbSspiLong (4=2*2) and can insert it at the correct position
             &"".into(), // 10. ibSSPI
             &"".into(), // ibAtchDBFile
-            &"".into(), // ibChangePassword
+            &self.new_password, // ibChangePassword
         ];
 
         let mut data_offset = cursor.position() as usize + var_data.len() * 2 * 2 + 6;
@@ -321,8 +349,8 @@ impl<'a> Encode<BytesMut> for LoginMessage<'a> {
 
             let new_position = cursor.position() as usize;
 
-            // prepare the password in MS-fashion
-            if i == 2 {
+            // prepare the password/new password in MS-fashion
+            if i == 2 || i == 12 {
                 let buffer = cursor.get_mut();
                 for byte in buffer.iter_mut().take(new_position).skip(data_offset) {
                     *byte = ((*byte << 4) & 0xf0 | (*byte >> 4) & 0x0f) ^ 0xA5;
@@ -456,7 +484,7 @@ mod tests {
                     let pos = cursor.position();
                     cursor.set_position(offset as u64);
 
-                    if $tag == "password" {
+                    if $tag == "password" || $tag == "new_password" {
                         let buffer = cursor.get_mut();
                         for byte in buffer
                             .iter_mut()
@@ -496,9 +524,9 @@ mod tests {
             let is = read_offset_length_bytes!();
             ret.integrated_security = if is.is_empty() { None } else { Some(is) };
             let _ = read_offset_length_string!(); // ibAtchDBFile
-            let _ = read_offset_length_string!(); // ibChangePassword
-                                                  // let _ = cursor.read_u32::<LittleEndian>()?;
-                                                  // cbSSPILong
+            ret.new_password = read_offset_length_string!("new_password").into();
+            // let _ = cursor.read_u32::<LittleEndian>()?;
+            // cbSSPILong
 
             if fea_ext_offset != 0 {
                 cursor.set_position((fea_ext_offset) as u64);
@@ -569,6 +597,27 @@ mod tests {
         assert_eq!(login, decoded);
     }
 
+    #[test]
+    fn login_message_with_password_change_round_trip() {
+        let mut payload = BytesMut::new();
+        let mut login = LoginMessage::new();
+        login.user_name("fake-user-name");
+        login.password("fake-old-pw");
+        login.change_password("fake-new-pw");
+        login
+            .clone()
+            .encode(&mut payload)
+            .expect("encode should succeed");
+
+        assert!(login
+            .option_flags_3
+            .contains(OptionFlag3::RequestChangePassword));
+
+        let decoded = LoginMessage::decode(&mut payload).expect("decode should succeed");
+
+        assert_eq!(login, decoded);
+    }
+
     #[test]
     fn specify_aad_token() {
         let mut login = LoginMessage::new();
@@ -1,4 +1,4 @@
-use super::{Decode, Encode, PacketHeader, PacketStatus, HEADER_BYTES};
+use super::{Decode, Encode, PacketHeader, PacketStatus, PacketType, HEADER_BYTES};
 use bytes::BytesMut;
 
 #[derive(Debug)]
@@ -16,6 +16,22 @@ impl Packet {
         self.header.status() == PacketStatus::EndOfMessage
     }
 
+    /// The SQL Server process ID (SPID) that produced this packet.
+    pub(crate) fn spid(&self) -> u16 {
+        self.header.spid()
+    }
+
+    /// The message type this packet belongs to, e.g. `TabularResult`.
+    pub(crate) fn packet_type(&self) -> PacketType {
+        self.header.r#type()
+    }
+
+    /// The number of payload bytes carried by this packet, excluding the
+    /// header.
+    pub(crate) fn payload_len(&self) -> usize {
+        self.payload.len()
+    }
+
     pub(crate) fn into_parts(self) -> (PacketHeader, BytesMut) {
         (self.header, self.payload)
     }
@@ -4,7 +4,7 @@ use crate::{tds, Error, Result};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 use bytes::{BufMut, BytesMut};
 use std::convert::TryFrom;
-use std::io::{Cursor, Read};
+use std::io::{Cursor, Read, Write};
 use tds::EncryptionLevel;
 use uuid::Uuid;
 
@@ -120,6 +120,19 @@ impl Encode<BytesMut> for PreloginMessage {
         fields.push((PRELOGIN_MARS, 0x01)); // MARS
         data_cursor.write_u8(self.mars as u8)?;
 
+        // instance name, e.g. "SQLEXPRESS", null-terminated. Sent even when
+        // unset (as a lone terminator) since some older servers use its
+        // presence, not just its content, to decide whether to route the
+        // connection to a specific instance instead of the default one.
+        fields.push((
+            PRELOGIN_INSTOPT,
+            self.instance_name.as_ref().map_or(0, |name| name.len()) as u16 + 1,
+        ));
+        if let Some(ref instance_name) = self.instance_name {
+            data_cursor.write_all(instance_name.as_bytes())?;
+        }
+        data_cursor.write_u8(0)?;
+
         // fed auth
         if self.fed_auth_required {
             fields.push((PRELOGIN_FEDAUTHREQUIRED, 0x01));
@@ -268,6 +281,21 @@ mod tests {
         assert_eq!(prelogin, decoded);
     }
 
+    #[test]
+    fn prelogin_with_instance_name_roundtrip() {
+        let mut payload = BytesMut::new();
+        let mut prelogin = PreloginMessage::new();
+        prelogin.instance_name = Some("SQLEXPRESS".into());
+        prelogin
+            .clone()
+            .encode(&mut payload)
+            .expect("encode should succeed");
+
+        let decoded = PreloginMessage::decode(&mut payload).expect("decode should succeed");
+
+        assert_eq!(prelogin, decoded);
+    }
+
     #[test]
     fn prelogin_with_fedauth_roundtrip() {
         let mut payload = BytesMut::new();
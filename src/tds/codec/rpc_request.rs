@@ -1,10 +1,15 @@
-use super::{AllHeaderTy, Encode, ALL_HEADERS_LEN_TX};
+use super::{AllHeaderTy, Encode, TypeInfo, ALL_HEADERS_LEN_TX};
 use crate::{tds::codec::ColumnData, BytesMutWithTypeInfo, Result};
 use bytes::{BufMut, BytesMut};
 use enumflags2::{bitflags, BitFlags};
 use std::borrow::BorrowMut;
 use std::borrow::Cow;
 
+/// Per-parameter status flags (`fByRefValue`, `fDefaultValue`, ...) sent with
+/// every [`RpcParam`], encoded on the wire as a single byte. Kept as its own
+/// `#[repr(u8)]` type, distinct from [`RpcOption`], so a caller can't
+/// accidentally pass a request-level flag where a parameter-level one is
+/// expected, or vice versa.
 #[bitflags]
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -15,6 +20,10 @@ pub enum RpcStatus {
     Encrypted = 1 << 3,
 }
 
+/// Request-level option flags (`fWithRecomp`, `fNoMetaData`, ...) sent once
+/// per [`TokenRpcRequest`], encoded on the wire as two bytes. Kept as its own
+/// `#[repr(u16)]` type, distinct from [`RpcStatus`], so the two widths can't
+/// be confused with one another.
 #[bitflags]
 #[repr(u16)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -51,6 +60,10 @@ pub struct RpcParam<'a> {
     pub name: Cow<'a, str>,
     pub flags: BitFlags<RpcStatus>,
     pub value: ColumnData<'a>,
+    /// Overrides the wire type `value` is encoded as, instead of the default
+    /// chosen by [`ColumnData::encode`] for a `None` type context (e.g. to
+    /// send a `String` as `varchar` rather than the default `nvarchar`).
+    pub type_info: Option<TypeInfo>,
 }
 
 /// 2.2.6.6 RPC Request
@@ -135,6 +148,11 @@ impl<'a> Encode<BytesMut> for RpcParam<'a> {
         dst.put_u8(self.flags.bits());
 
         let mut dst_fi = BytesMutWithTypeInfo::new(dst);
+
+        if let Some(ref type_info) = self.type_info {
+            dst_fi = dst_fi.with_type_info(type_info);
+        }
+
         self.value.encode(&mut dst_fi)?;
 
         let dst: &mut [u8] = dst.borrow_mut();
@@ -119,8 +119,8 @@ pub struct BaseMetaDataColumn {
 }
 
 impl BaseMetaDataColumn {
-    pub(crate) fn null_value(&self) -> ColumnData<'static> {
-        match &self.ty {
+    pub(crate) fn null_value(&self) -> crate::Result<ColumnData<'static>> {
+        Ok(match &self.ty {
             TypeInfo::FixedLen(ty) => match ty {
                 FixedLenType::Null => ColumnData::I32(None),
                 FixedLenType::Int1 => ColumnData::U8(None),
@@ -159,11 +159,29 @@ impl BaseMetaDataColumn {
                 VarLenType::NVarchar => ColumnData::String(None),
                 VarLenType::NChar => ColumnData::String(None),
                 VarLenType::Xml => ColumnData::Xml(None),
-                VarLenType::Udt => todo!("User-defined types not supported"),
+                VarLenType::Udt => {
+                    return Err(Error::Unsupported {
+                        feature: "UDT columns".into(),
+                        hint: "CLR user-defined types aren't decoded by this driver; cast the column to varbinary or a built-in type in the query".into(),
+                    })
+                }
                 VarLenType::Text => ColumnData::String(None),
                 VarLenType::Image => ColumnData::Binary(None),
                 VarLenType::NText => ColumnData::String(None),
-                VarLenType::SSVariant => todo!(),
+                VarLenType::SSVariant => {
+                    return Err(Error::Unsupported {
+                        feature: "sql_variant columns".into(),
+                        hint: "cast the column to a concrete type in the query".into(),
+                    })
+                }
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Numeric => ColumnData::Numeric(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Decimal => ColumnData::Numeric(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::VarChar => ColumnData::String(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Binary => ColumnData::Binary(None),
             },
             TypeInfo::VarLenSizedPrecision { ty, .. } => match ty {
                 VarLenType::Guid => ColumnData::Guid(None),
@@ -189,14 +207,32 @@ impl BaseMetaDataColumn {
                 VarLenType::NVarchar => ColumnData::String(None),
                 VarLenType::NChar => ColumnData::String(None),
                 VarLenType::Xml => ColumnData::Xml(None),
-                VarLenType::Udt => todo!("User-defined types not supported"),
+                VarLenType::Udt => {
+                    return Err(Error::Unsupported {
+                        feature: "UDT columns".into(),
+                        hint: "CLR user-defined types aren't decoded by this driver; cast the column to varbinary or a built-in type in the query".into(),
+                    })
+                }
                 VarLenType::Text => ColumnData::String(None),
                 VarLenType::Image => ColumnData::Binary(None),
                 VarLenType::NText => ColumnData::String(None),
-                VarLenType::SSVariant => todo!(),
+                VarLenType::SSVariant => {
+                    return Err(Error::Unsupported {
+                        feature: "sql_variant columns".into(),
+                        hint: "cast the column to a concrete type in the query".into(),
+                    })
+                }
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Numeric => ColumnData::Numeric(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Decimal => ColumnData::Numeric(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::VarChar => ColumnData::String(None),
+                #[cfg(feature = "legacy-types")]
+                VarLenType::Binary => ColumnData::Binary(None),
             },
             TypeInfo::Xml { .. } => ColumnData::Xml(None),
-        }
+        })
     }
 }
 
@@ -304,9 +340,13 @@ impl TokenColMetaData<'static> {
 
 impl<'a> TokenColMetaData<'a> {
     pub(crate) fn columns(&self) -> impl Iterator<Item = Column> + '_ {
-        self.columns.iter().map(|x| Column {
-            name: x.col_name.to_string(),
-            column_type: ColumnType::from(&x.base.ty),
+        self.columns.iter().map(|x| {
+            Column::new(
+                x.col_name.as_ref(),
+                ColumnType::from(&x.base.ty),
+                x.base.flags,
+                x.base.ty.collation(),
+            )
         })
     }
 }
@@ -90,6 +90,70 @@ impl TokenError {
     pub fn line(&self) -> u32 {
         self.line
     }
+
+    /// Whether the error is likely to succeed if the same statement is
+    /// retried, such as a deadlock or an Azure SQL Database throttling or
+    /// failover error.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self.code,
+            1205 | 4060 | 40197 | 40501 | 40613 | 49918 | 49919 | 49920
+        )
+    }
+
+    /// Whether the error is a constraint or index violation, such as a
+    /// unique key, primary key or foreign key violation.
+    pub fn is_constraint_violation(&self) -> bool {
+        matches!(self.code, 547 | 2601 | 2627)
+    }
+
+    /// Whether the error indicates that authentication with the server
+    /// failed, e.g. because of a bad login or password.
+    ///
+    /// For SQL Server error 18456 (bad username/password), [`state`]
+    /// carries the specific reason - e.g. state 8 for a wrong password or
+    /// state 18 for an account whose password must be changed before it can
+    /// log in, which also makes [`is_password_expired`] return `true`.
+    ///
+    /// [`state`]: Self::state
+    /// [`is_password_expired`]: Self::is_password_expired
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(self.code, 18452 | 18456)
+    }
+
+    /// Whether the error indicates the account's password has expired and
+    /// must be changed before the account can log in, i.e. SQL Server error
+    /// 18488, or error 18456 in state 18.
+    pub fn is_password_expired(&self) -> bool {
+        self.code == 18488 || (self.code == 18456 && self.state == 18)
+    }
+
+    /// Whether the error indicates the database this login requested
+    /// couldn't be opened - it doesn't exist, is offline, or this login
+    /// lacks access to it - i.e. SQL Server error 4060.
+    pub fn is_database_unavailable(&self) -> bool {
+        self.code == 4060
+    }
+
+    /// Whether the error indicates the statement was chosen as the deadlock
+    /// victim and rolled back, i.e. SQL Server error 1205. Safe to retry.
+    pub fn is_deadlock_victim(&self) -> bool {
+        self.code == 1205
+    }
+
+    /// Whether the error indicates that a DDL statement was rejected
+    /// because it cannot run inside a user transaction, e.g. `CREATE
+    /// DATABASE` issued inside a `BEGIN TRAN`.
+    pub fn is_ddl_in_transaction(&self) -> bool {
+        self.code == 226
+    }
+
+    /// Whether the error is severe enough that the server will have closed
+    /// the connection. Classes 20 and above are fatal; anything below is
+    /// safe to keep using the same connection for further statements.
+    pub fn is_fatal(&self) -> bool {
+        self.class >= 20
+    }
 }
 
 impl fmt::Display for TokenError {
@@ -1,4 +1,4 @@
-use crate::{SqlReadBytes, FEA_EXT_FEDAUTH, FEA_EXT_TERMINATOR};
+use crate::{Error, SqlReadBytes, FEA_EXT_FEDAUTH, FEA_EXT_TERMINATOR};
 use futures_util::AsyncReadExt;
 
 #[derive(Debug)]
@@ -38,15 +38,109 @@ impl TokenFeatureExtAck {
                 } else if data_len == 0 {
                     None
                 } else {
-                    panic!("invalid Feature_Ext_Ack token");
+                    return Err(Error::Protocol(
+                        format!("invalid FedAuth Feature_Ext_Ack data length: {}", data_len).into(),
+                    ));
                 };
 
                 features.push(FeatureAck::FedAuth(FedAuthAck::SecurityToken { nonce }))
             } else {
-                unimplemented!("unsupported feature {}", feature_id)
+                // A server can only ack a feature the client asked for in its
+                // own FeatureExt request, and this crate only ever requests
+                // FedAuth today - but a future login change adding another
+                // feature id here without a matching arm shouldn't turn into
+                // a hard panic on every connection to a server that acks it.
+                return Err(Error::Protocol(
+                    format!("unsupported Feature_Ext_Ack feature id: {}", feature_id).into(),
+                ));
             }
         }
 
         Ok(TokenFeatureExtAck { features })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_read_bytes::test_utils::IntoSqlReadBytes;
+    use bytes::{BufMut, BytesMut};
+
+    #[tokio::test]
+    async fn only_a_terminator_decodes_to_no_features() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(FEA_EXT_TERMINATOR);
+
+        let ack = TokenFeatureExtAck::decode(&mut buf.into_sql_read_bytes())
+            .await
+            .expect("decode must succeed");
+
+        assert!(ack.features.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fedauth_with_a_nonce_decodes() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(FEA_EXT_FEDAUTH);
+        buf.put_u32_le(32);
+        buf.put_slice(&[0xAB; 32]);
+        buf.put_u8(FEA_EXT_TERMINATOR);
+
+        let ack = TokenFeatureExtAck::decode(&mut buf.into_sql_read_bytes())
+            .await
+            .expect("decode must succeed");
+
+        match ack.features.as_slice() {
+            [FeatureAck::FedAuth(FedAuthAck::SecurityToken { nonce: Some(n) })] => {
+                assert_eq!(n, &[0xAB; 32]);
+            }
+            other => panic!("unexpected features: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn fedauth_without_a_nonce_decodes() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(FEA_EXT_FEDAUTH);
+        buf.put_u32_le(0);
+        buf.put_u8(FEA_EXT_TERMINATOR);
+
+        let ack = TokenFeatureExtAck::decode(&mut buf.into_sql_read_bytes())
+            .await
+            .expect("decode must succeed");
+
+        match ack.features.as_slice() {
+            [FeatureAck::FedAuth(FedAuthAck::SecurityToken { nonce: None })] => {}
+            other => panic!("unexpected features: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn fedauth_with_an_unexpected_data_length_is_a_protocol_error() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(FEA_EXT_FEDAUTH);
+        buf.put_u32_le(5);
+
+        let err = TokenFeatureExtAck::decode(&mut buf.into_sql_read_bytes())
+            .await
+            .expect_err("decode must fail");
+
+        assert!(matches!(err, Error::Protocol(_)));
+    }
+
+    #[tokio::test]
+    async fn an_unknown_feature_id_is_a_protocol_error_not_a_panic() {
+        // A server only acks a feature it was asked for, and this crate only
+        // ever asks for FedAuth - but any other id (e.g. UTF8_SUPPORT or
+        // SESSIONRECOVERY, requested by newer clients) must not crash a
+        // connection to a server that sends one back.
+        let mut buf = BytesMut::new();
+        buf.put_u8(0x05);
+
+        let err = TokenFeatureExtAck::decode(&mut buf.into_sql_read_bytes())
+            .await
+            .expect_err("decode must fail");
+
+        assert!(matches!(err, Error::Protocol(_)));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::SqlReadBytes;
 
-#[allow(dead_code)] // we might want to debug the values
-#[derive(Debug)]
+/// An informational message sent by the server, e.g. from a `PRINT` or
+/// `RAISERROR` with a severity below 11.
+#[derive(Debug, Clone)]
 pub struct TokenInfo {
     /// info number
     pub(crate) number: u32,
@@ -40,4 +41,42 @@ impl TokenInfo {
             line,
         })
     }
+
+    /// The info number.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// The error state, used as a modifier to the info number.
+    pub fn state(&self) -> u8 {
+        self.state
+    }
+
+    /// The class (severity) of the message. A class of less than 10
+    /// indicates an informational message.
+    pub fn class(&self) -> u8 {
+        self.class
+    }
+
+    /// The message text.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The server name.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    /// The name of the stored procedure that sent the message.
+    pub fn procedure(&self) -> &str {
+        &self.procedure
+    }
+
+    /// The line number in the SQL batch or stored procedure that sent the
+    /// message. Line numbers begin at 1. If the line number is not
+    /// applicable, the value is 0.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
 }
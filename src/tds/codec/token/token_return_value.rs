@@ -1,13 +1,21 @@
 use super::BaseMetaDataColumn;
 use crate::{tds::codec::ColumnData, Error, SqlReadBytes};
 
+/// An `OUTPUT` parameter or a user-defined function's return value. A
+/// stored procedure's `RETURN` status is a separate `RETURNSTATUS` token.
 #[derive(Debug)]
 pub struct TokenReturnValue {
+    /// The 1-based position of the parameter in the call, or `0` for a
+    /// user-defined function's return value.
     pub param_ordinal: u16,
+    /// The parameter name, including its `@` prefix, or empty for a
+    /// user-defined function's return value.
     pub param_name: String,
     /// return value of user defined function
     pub udf: bool,
+    /// The type of the returned value.
     pub meta: BaseMetaDataColumn,
+    /// The returned value itself.
     pub value: ColumnData<'static>,
 }
 
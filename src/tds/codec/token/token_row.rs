@@ -8,7 +8,7 @@ use futures::io::AsyncReadExt;
 pub use into_row::IntoRow;
 
 /// A row of data.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
 pub struct TokenRow<'a> {
     data: Vec<ColumnData<'a>>,
 }
@@ -88,7 +88,9 @@ impl TokenRow<'static> {
     where
         R: SqlReadBytes + Unpin,
     {
-        let col_meta = src.context().last_meta().unwrap();
+        let col_meta = src.context().last_meta().ok_or_else(|| {
+            crate::Error::Protocol("Got a row token without a preceding column metadata".into())
+        })?;
 
         let mut row = Self {
             data: Vec::with_capacity(col_meta.columns.len()),
@@ -108,7 +110,9 @@ impl TokenRow<'static> {
     where
         R: SqlReadBytes + Unpin,
     {
-        let col_meta = src.context().last_meta().unwrap();
+        let col_meta = src.context().last_meta().ok_or_else(|| {
+            crate::Error::Protocol("Got a row token without a preceding column metadata".into())
+        })?;
         let row_bitmap = RowBitmap::decode(src, col_meta.columns.len()).await?;
 
         let mut row = Self {
@@ -117,7 +121,7 @@ impl TokenRow<'static> {
 
         for (i, column) in col_meta.columns.iter().enumerate() {
             let data = if row_bitmap.is_null(i) {
-                column.base.null_value()
+                column.base.null_value()?
             } else {
                 ColumnData::decode(src, &column.base.ty).await?
             };
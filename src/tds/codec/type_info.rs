@@ -99,7 +99,12 @@ impl Encode<BytesMut> for VarLenContext {
                 dst.put_u32_le(self.len() as u32);
             }
             VarLenType::Xml => (),
-            typ => todo!("encoding {:?} is not supported yet", typ),
+            typ => {
+                return Err(Error::Unsupported {
+                    feature: format!("encoding a {:?} column", typ).into(),
+                    hint: "this column type has no wire encoding implemented; cast the value to a supported type before binding it".into(),
+                })
+            }
         }
 
         if let Some(collation) = self.collation() {
@@ -157,11 +162,15 @@ uint_enum! {
         // not supported yet
         SSVariant = 0x62, // legacy types (not supported since post-7.2):
                           // Char = 0x2F,
-                          // Binary = 0x2D,
                           // VarBinary = 0x25,
-                          // VarChar = 0x27,
-                          // Numeric = 0x3F,
-                          // Decimal = 0x37
+        #[cfg(feature = "legacy-types")]
+        Binary = 0x2D,
+        #[cfg(feature = "legacy-types")]
+        VarChar = 0x27,
+        #[cfg(feature = "legacy-types")]
+        Numeric = 0x3F,
+        #[cfg(feature = "legacy-types")]
+        Decimal = 0x37,
     }
 }
 
@@ -197,11 +206,15 @@ uint_enum! {
         // not supported yet
         SSVariant = 0x62, // legacy types (not supported since post-7.2):
                           // Char = 0x2F,
-                          // Binary = 0x2D,
                           // VarBinary = 0x25,
-                          // VarChar = 0x27,
-                          // Numeric = 0x3F,
-                          // Decimal = 0x37
+        #[cfg(feature = "legacy-types")]
+        Binary = 0x2D,
+        #[cfg(feature = "legacy-types")]
+        VarChar = 0x27,
+        #[cfg(feature = "legacy-types")]
+        Numeric = 0x3F,
+        #[cfg(feature = "legacy-types")]
+        Decimal = 0x37,
     }
 }
 
@@ -307,16 +320,35 @@ impl TypeInfo {
                     | VarLenType::Guid
                     | VarLenType::Money
                     | VarLenType::Datetimen => src.read_u8().await? as usize,
+                    #[cfg(feature = "legacy-types")]
+                    VarLenType::Numeric | VarLenType::Decimal => src.read_u8().await? as usize,
                     VarLenType::NChar
                     | VarLenType::BigChar
                     | VarLenType::NVarchar
                     | VarLenType::BigVarChar
                     | VarLenType::BigBinary
                     | VarLenType::BigVarBin => src.read_u16_le().await? as usize,
+                    // Pre-7.2 `VARCHAR`/`BINARY` (0x27/0x2D) use the same
+                    // one-byte length as their fixed-length siblings from
+                    // that era, unlike the `BIGVARCHAR`/`BIGBINARY` types
+                    // that replaced them.
+                    #[cfg(feature = "legacy-types")]
+                    VarLenType::VarChar | VarLenType::Binary => src.read_u8().await? as usize,
                     VarLenType::Image | VarLenType::Text | VarLenType::NText => {
                         src.read_u32_le().await? as usize
                     }
-                    _ => todo!("not yet implemented for {:?}", ty),
+                    VarLenType::Udt => {
+                        return Err(Error::Unsupported {
+                            feature: "UDT columns".into(),
+                            hint: "CLR user-defined types aren't decoded by this driver; cast the column to varbinary or a built-in type in the query".into(),
+                        })
+                    }
+                    _ => {
+                        return Err(Error::Unsupported {
+                            feature: format!("decoding a {:?} column", ty).into(),
+                            hint: "this column type has no wire decoding implemented".into(),
+                        })
+                    }
                 };
 
                 let collation = match ty {
@@ -331,6 +363,13 @@ impl TypeInfo {
 
                         Some(Collation::new(info, sort_id))
                     }
+                    #[cfg(feature = "legacy-types")]
+                    VarLenType::VarChar => {
+                        let info = src.read_u32_le().await?;
+                        let sort_id = src.read_u8().await?;
+
+                        Some(Collation::new(info, sort_id))
+                    }
                     _ => None,
                 };
 
@@ -346,6 +385,18 @@ impl TypeInfo {
                             scale,
                         }
                     }
+                    #[cfg(feature = "legacy-types")]
+                    VarLenType::Numeric | VarLenType::Decimal => {
+                        let precision = src.read_u8().await?;
+                        let scale = src.read_u8().await?;
+
+                        TypeInfo::VarLenSizedPrecision {
+                            size: len,
+                            ty,
+                            precision,
+                            scale,
+                        }
+                    }
                     _ => {
                         let cx = VarLenContext::new(ty, len, collation);
                         TypeInfo::VarLenSized(cx)
@@ -356,6 +407,16 @@ impl TypeInfo {
             }
         }
     }
+
+    /// The collation this type carries, if any. Only a `VarLenSized` type
+    /// (`char`/`varchar`/`text` and their `n`-prefixed counterparts) has
+    /// one; every other type has no collation of its own.
+    pub(crate) fn collation(&self) -> Option<Collation> {
+        match self {
+            TypeInfo::VarLenSized(ctx) => ctx.collation(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -398,4 +459,15 @@ mod tests {
             assert_eq!(nti, ti)
         }
     }
+
+    #[test]
+    fn encoding_an_unsupported_type_reports_which_one() {
+        let ctx = VarLenContext::new(VarLenType::Udt, 0, None);
+        let mut buf = BytesMut::new();
+
+        match ctx.encode(&mut buf) {
+            Err(Error::Unsupported { feature, .. }) => assert!(feature.contains("Udt")),
+            other => panic!("expected Error::Unsupported, got {:?}", other),
+        }
+    }
 }
@@ -11,7 +11,12 @@ use encoding::{self, Encoding};
 
 use crate::error::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// A column's collation, as negotiated between client and server: the
+/// locale, comparison flags and sort order that decide how `char`/`varchar`
+/// data is compared and, via [`encoding`], how it's decoded.
+///
+/// [`encoding`]: Collation::encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Collation {
     /// LCID ColFlags Version
     info: u32,
@@ -20,6 +25,8 @@ pub struct Collation {
 }
 
 impl Collation {
+    /// Builds a `Collation` from the raw `info` (`LCID`/`ColFlags`/`Version`
+    /// packed into one `u32`) and `sort_id` bytes sent by the server.
     pub fn new(info: u32, sort_id: u8) -> Self {
         Self { info, sort_id }
     }
@@ -29,14 +36,36 @@ impl Collation {
         (self.info & 0xffff) as u16
     }
 
+    /// The legacy sort ID, used instead of the LCID to pick an encoding for
+    /// collations that predate LCID-based collations. `0` means the LCID
+    /// should be used instead - see [`encoding`].
+    ///
+    /// [`encoding`]: Collation::encoding
     pub fn sort_id(&self) -> u8 {
         self.sort_id
     }
 
+    /// The raw `LCID`/`ColFlags`/`Version` bytes packed into a `u32`, as
+    /// sent by the server.
     pub fn info(&self) -> u32 {
         self.info
     }
 
+    /// The `ColFlags` part of the collation, e.g. whether comparisons are
+    /// case-, accent-, kana- or width-insensitive, or the column is
+    /// `BINARY`/`BINARY2` collated. See the flag bit layout in
+    /// `MS-SQLTDS` for how to interpret individual bits.
+    pub fn flags(&self) -> u8 {
+        ((self.info >> 20) & 0xff) as u8
+    }
+
+    /// The collation version, the top 4 bits of [`info`].
+    ///
+    /// [`info`]: Collation::info
+    pub fn version(&self) -> u8 {
+        ((self.info >> 28) & 0xf) as u8
+    }
+
     /// return an encoding for a given collation
     pub fn encoding(&self) -> crate::Result<&'static (dyn Encoding + Send + Sync)> {
         let res = if self.sort_id == 0 {
@@ -56,6 +85,16 @@ impl Collation {
             )
         })
     }
+
+    /// The name of the encoding the driver will use to decode `varchar`/
+    /// `char` data in a column with this collation, or `None` if the
+    /// collation isn't one this driver can decode - see [`encoding`] for the
+    /// error that would be returned when actually decoding such a column.
+    ///
+    /// [`encoding`]: Collation::encoding
+    pub fn encoding_name(&self) -> Option<&'static str> {
+        self.encoding().ok().map(|encoding| encoding.name())
+    }
 }
 
 impl fmt::Display for Collation {
@@ -1,4 +1,6 @@
-use super::codec::*;
+use super::{codec::*, Collation, ServerKind};
+use crate::EncryptionLevel;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// Context, that might be required to make sure we understand and are understood by the server
@@ -10,6 +12,18 @@ pub(crate) struct Context {
     transaction_desc: [u8; 8],
     last_meta: Option<Arc<TokenColMetaData<'static>>>,
     spn: Option<String>,
+    spid: u16,
+    lenient_tokens: bool,
+    escalate_info_codes: Arc<HashSet<u32>>,
+    collation: Option<Collation>,
+    host: String,
+    instance_name: Option<String>,
+    affinity_key: Option<String>,
+    server_kind: ServerKind,
+    encryption: EncryptionLevel,
+    mars: bool,
+    #[cfg(feature = "chrono")]
+    datetime_interpretation: crate::time::chrono::DateTimeInterpretation,
 }
 
 impl Context {
@@ -21,6 +35,18 @@ impl Context {
             transaction_desc: [0; 8],
             last_meta: None,
             spn: None,
+            spid: 0,
+            lenient_tokens: false,
+            escalate_info_codes: Arc::new(HashSet::new()),
+            collation: None,
+            host: String::new(),
+            instance_name: None,
+            affinity_key: None,
+            server_kind: ServerKind::SqlServer,
+            encryption: EncryptionLevel::NotSupported,
+            mars: false,
+            #[cfg(feature = "chrono")]
+            datetime_interpretation: crate::time::chrono::DateTimeInterpretation::default(),
         }
     }
 
@@ -58,6 +84,14 @@ impl Context {
         self.version
     }
 
+    /// Records the TDS version the server actually acknowledged in its
+    /// `LOGINACK`, which is what ends up governing wire behavior for the
+    /// rest of the connection - not necessarily the version this driver
+    /// asked for in `LOGIN7`.
+    pub fn set_version(&mut self, version: FeatureLevel) {
+        self.version = version;
+    }
+
     pub fn set_spn(&mut self, host: impl AsRef<str>, port: u16) {
         self.spn = Some(format!("MSSQLSvc/{}:{}", host.as_ref(), port));
     }
@@ -66,4 +100,105 @@ impl Context {
     pub fn spn(&self) -> &str {
         self.spn.as_deref().unwrap_or("")
     }
+
+    pub fn set_spid(&mut self, spid: u16) {
+        self.spid = spid;
+    }
+
+    pub fn spid(&self) -> u16 {
+        self.spid
+    }
+
+    pub fn set_lenient_tokens(&mut self, lenient: bool) {
+        self.lenient_tokens = lenient;
+    }
+
+    pub fn lenient_tokens(&self) -> bool {
+        self.lenient_tokens
+    }
+
+    pub fn set_escalate_info_codes(&mut self, codes: Arc<HashSet<u32>>) {
+        self.escalate_info_codes = codes;
+    }
+
+    pub fn should_escalate_info(&self, number: u32) -> bool {
+        self.escalate_info_codes.contains(&number)
+    }
+
+    pub fn set_collation(&mut self, collation: Option<Collation>) {
+        self.collation = collation;
+    }
+
+    /// The collation the server negotiated for this connection, received via
+    /// an `EnvChange::SqlCollation` token. `None` until the server has sent
+    /// one, which normally happens right after login.
+    pub fn collation(&self) -> Option<Collation> {
+        self.collation
+    }
+
+    pub fn set_host_info(&mut self, host: String, instance_name: Option<String>) {
+        self.host = host;
+        self.instance_name = instance_name;
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn instance_name(&self) -> Option<&str> {
+        self.instance_name.as_deref()
+    }
+
+    pub fn set_affinity_key(&mut self, key: Option<String>) {
+        self.affinity_key = key;
+    }
+
+    pub fn affinity_key(&self) -> Option<&str> {
+        self.affinity_key.as_deref()
+    }
+
+    pub fn set_server_kind(&mut self, kind: ServerKind) {
+        self.server_kind = kind;
+    }
+
+    /// Which flavor of TDS server this connection is talking to, detected
+    /// from the `LOGINACK` sent during login. Still [`ServerKind::SqlServer`]
+    /// (the default) until login completes.
+    pub fn server_kind(&self) -> ServerKind {
+        self.server_kind
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn set_datetime_interpretation(
+        &mut self,
+        interpretation: crate::time::chrono::DateTimeInterpretation,
+    ) {
+        self.datetime_interpretation = interpretation;
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn datetime_interpretation(&self) -> crate::time::chrono::DateTimeInterpretation {
+        self.datetime_interpretation
+    }
+
+    pub fn set_encryption(&mut self, encryption: EncryptionLevel) {
+        self.encryption = encryption;
+    }
+
+    /// The encryption level negotiated in `PRELOGIN`. [`EncryptionLevel::NotSupported`]
+    /// (the default) until login completes.
+    pub fn encryption(&self) -> EncryptionLevel {
+        self.encryption
+    }
+
+    pub fn set_mars(&mut self, mars: bool) {
+        self.mars = mars;
+    }
+
+    /// Whether the server offered Multiple Active Result Sets in `PRELOGIN`.
+    /// This driver never requests it, so the connection doesn't actually use
+    /// it either way; this only reflects what the server was willing to do.
+    pub fn mars(&self) -> bool {
+        self.mars
+    }
 }
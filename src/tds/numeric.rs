@@ -202,6 +202,25 @@ impl Display for Numeric {
 
 impl Eq for Numeric {}
 
+impl std::hash::Hash for Numeric {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Match PartialEq above, which treats e.g. a scale-2 and a scale-3
+        // Numeric with the same underlying value as equal: strip trailing
+        // zero digits until the scale can't be reduced any further, so two
+        // equal values always normalize to the same (value, scale) pair.
+        let mut value = self.value;
+        let mut scale = self.scale;
+
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+
+        value.hash(state);
+        scale.hash(state);
+    }
+}
+
 impl From<Numeric> for f64 {
     fn from(n: Numeric) -> f64 {
         n.dec_part() as f64 / n.pow_scale() as f64 + n.int_part() as f64
@@ -263,6 +282,23 @@ mod decimal {
                 Numeric::new_with_scale(value, self_.scale() as u8)
             });
     );
+
+    #[cfg(feature = "tds73")]
+    into_sql!(self_,
+            Decimal: (ColumnData::Numeric, {
+                let unpacked = self_.unpack();
+
+                let mut value = (((unpacked.hi as u128) << 64)
+                                 + ((unpacked.mid as u128) << 32)
+                                 + unpacked.lo as u128) as i128;
+
+                if self_.is_sign_negative() {
+                    value = -value;
+                }
+
+                Numeric::new_with_scale(value, self_.scale() as u8)
+            });
+    );
 }
 
 #[cfg(feature = "bigdecimal")]
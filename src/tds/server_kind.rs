@@ -0,0 +1,46 @@
+/// Which flavor of TDS server this connection is talking to, detected from
+/// the server's `LOGINACK` program name and, for LocalDB, the connect
+/// target itself.
+///
+/// The different flavors don't all support the same T-SQL surface (Azure
+/// SQL Database, for instance, doesn't support `USE`), so knowing which one
+/// is on the other end lets the driver give a clear, local error instead of
+/// forwarding a confusing server-side one, or in some cases avoid sending
+/// the unsupported request at all.
+///
+/// Detection relies on a well-known but undocumented value in the
+/// `LOGINACK` program name; there's been no live instance of every kind
+/// available while writing this to confirm every case, so treat an
+/// unexpected [`ServerKind::SqlServer`] result as "unrecognized", not
+/// necessarily "definitely on-premises".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerKind {
+    /// A regular on-premises or IaaS SQL Server instance.
+    SqlServer,
+    /// Azure SQL Database. Requires the database to be chosen at login
+    /// time via [`Config::database`] and doesn't support `USE` to switch
+    /// databases within a session.
+    ///
+    /// [`Config::database`]: crate::Config::database
+    AzureSqlDatabase,
+    /// A SQL Server Express LocalDB instance, addressed through the
+    /// `(localdb)\instance` connect syntax.
+    LocalDb,
+}
+
+impl ServerKind {
+    pub(crate) fn detect(host: &str, instance_name: Option<&str>, prog_name: &str) -> Self {
+        let is_localdb = host.to_lowercase().starts_with("(localdb)")
+            || instance_name
+                .map(|name| name.eq_ignore_ascii_case("mssqllocaldb"))
+                .unwrap_or(false);
+
+        if is_localdb {
+            Self::LocalDb
+        } else if prog_name.eq_ignore_ascii_case("microsoft sql azure") {
+            Self::AzureSqlDatabase
+        } else {
+            Self::SqlServer
+        }
+    }
+}
@@ -1,5 +1,7 @@
 mod query;
+mod raw_query;
 mod token;
 
 pub use query::*;
+pub use raw_query::*;
 pub use token::*;
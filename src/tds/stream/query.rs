@@ -1,5 +1,9 @@
 use crate::tds::stream::ReceivedToken;
-use crate::{row::ColumnType, Column, Row};
+use crate::{
+    row::ColumnType,
+    tds::codec::{TokenInfo, TokenReturnValue},
+    Column, Error, Row,
+};
 use futures::{
     ready,
     stream::{BoxStream, Peekable},
@@ -10,10 +14,12 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{self, Poll},
+    time::{Duration, Instant},
 };
 
-/// A set of `Streams` of [`QueryItem`] values, which can be either result
-/// metadata or a row.
+/// A set of `Streams` of [`QueryItem`] values, which can be result metadata,
+/// a row, or an informational message such as a `PRINT` or `RAISERROR`,
+/// arriving in the same order the server sent them.
 ///
 /// The `QueryStream` needs to be polled empty before sending another query to
 /// the [`Client`], failing to do so causes a flush before the next query,
@@ -30,7 +36,8 @@ use std::{
 /// might be more convenient to use.
 ///
 /// The struct provides non-streaming APIs with [`into_results`],
-/// [`into_first_result`] and [`into_row`].
+/// [`into_first_result`], [`into_row`] and, for bounding how much of a
+/// result set gets materialized, [`into_results_limited`].
 ///
 /// # Example
 ///
@@ -78,6 +85,10 @@ use std::{
 ///         QueryItem::Row(row) => {
 ///             assert_eq!(Some(2), row.get(0));
 ///         }
+///         // messages from e.g. PRINT or RAISERROR arrive in-order as well
+///         QueryItem::Info(info) => {
+///             println!("{}", info.message());
+///         }
 ///     }
 /// }
 /// # Ok(())
@@ -89,10 +100,13 @@ use std::{
 /// [`into_results`]: struct.QueryStream.html#method.into_results
 /// [`into_first_result`]: struct.QueryStream.html#method.into_first_result
 /// [`into_row`]: struct.QueryStream.html#method.into_row
+/// [`into_results_limited`]: struct.QueryStream.html#method.into_results_limited
 pub struct QueryStream<'a> {
     token_stream: Peekable<BoxStream<'a, crate::Result<ReceivedToken>>>,
     columns: Option<Arc<Vec<Column>>>,
     result_set_index: Option<usize>,
+    created_at: Instant,
+    first_row_at: Option<Instant>,
 }
 
 impl<'a> Debug for QueryStream<'a> {
@@ -112,9 +126,21 @@ impl<'a> QueryStream<'a> {
             token_stream: token_stream.peekable(),
             columns: None,
             result_set_index: None,
+            created_at: Instant::now(),
+            first_row_at: None,
         }
     }
 
+    /// How long it took from creating this stream - i.e. from sending the
+    /// query - until the first [`QueryItem::Row`] arrived, or `None` if no
+    /// row has arrived yet. Meant for measuring time-to-first-row in a UI
+    /// that renders rows as they stream in, where that matters more than
+    /// the time to read the whole result set.
+    pub fn time_to_first_row(&self) -> Option<Duration> {
+        self.first_row_at
+            .map(|first_row_at| first_row_at.duration_since(self.created_at))
+    }
+
     /// Moves the stream forward until having result metadata, stream end or an
     /// error.
     pub(crate) async fn forward_to_metadata(&mut self) -> crate::Result<()> {
@@ -238,6 +264,7 @@ impl<'a> QueryStream<'a> {
                     results.push(previous_result.take().unwrap());
                     result = None;
                 }
+                (QueryItem::Info(_), _) | (QueryItem::ReturnValue(_), _) => (),
             }
         }
 
@@ -257,6 +284,60 @@ impl<'a> QueryStream<'a> {
         Ok(rows)
     }
 
+    /// Like [`into_results`], but stops materializing rows for a result set
+    /// once it holds `max_rows`. The stream is still drained to completion,
+    /// so the connection stays usable for the next query, but rows past the
+    /// limit are dropped instead of being collected. The second element of
+    /// the returned tuple is `true` if any result set was truncated this
+    /// way.
+    ///
+    /// Useful as a guardrail against accidentally materializing an
+    /// unbounded `SELECT` in memory.
+    ///
+    /// [`into_results`]: struct.QueryStream.html#method.into_results
+    pub async fn into_results_limited(
+        mut self,
+        max_rows: usize,
+    ) -> crate::Result<(Vec<Vec<Row>>, bool)> {
+        let mut results: Vec<Vec<Row>> = Vec::new();
+        let mut result: Option<Vec<Row>> = None;
+        let mut truncated = false;
+
+        while let Some(item) = self.try_next().await? {
+            match (item, &mut result) {
+                (QueryItem::Row(row), None) => {
+                    if max_rows > 0 {
+                        result = Some(vec![row]);
+                    } else {
+                        result = Some(Vec::new());
+                        truncated = true;
+                    }
+                }
+                (QueryItem::Row(row), Some(ref mut result)) => {
+                    if result.len() < max_rows {
+                        result.push(row);
+                    } else {
+                        truncated = true;
+                    }
+                }
+                (QueryItem::Metadata(_), None) => {
+                    result = Some(Vec::new());
+                }
+                (QueryItem::Metadata(_), ref mut previous_result) => {
+                    results.push(previous_result.take().unwrap());
+                    result = None;
+                }
+                (QueryItem::Info(_), _) | (QueryItem::ReturnValue(_), _) => (),
+            }
+        }
+
+        if let Some(result) = result {
+            results.push(result);
+        }
+
+        Ok((results, truncated))
+    }
+
     /// Collects the first row from the output of the first query, dropping any
     /// further rows.
     pub async fn into_row(self) -> crate::Result<Option<Row>> {
@@ -265,12 +346,63 @@ impl<'a> QueryStream<'a> {
         Ok(results.next())
     }
 
+    /// Collects the single row from the output of the first query, failing
+    /// if it produced zero rows or more than one, instead of the silent
+    /// `rows.get(0)` this is meant to replace.
+    pub async fn into_single_row(self) -> crate::Result<Row> {
+        let mut rows = self.into_first_result().await?.into_iter();
+
+        let row = rows.next().ok_or_else(|| Error::UnexpectedRowCount {
+            expected: "exactly one".into(),
+            actual: 0,
+        })?;
+
+        if rows.next().is_some() {
+            return Err(Error::UnexpectedRowCount {
+                expected: "exactly one".into(),
+                actual: 2 + rows.count(),
+            });
+        }
+
+        Ok(row)
+    }
+
+    /// Collects the single row from the output of the first query, if any,
+    /// failing if it produced more than one row. Unlike [`into_row`], which
+    /// silently keeps only the first row, this treats more than one row as
+    /// a result-shape mismatch.
+    ///
+    /// [`into_row`]: #method.into_row
+    pub async fn into_optional_row(self) -> crate::Result<Option<Row>> {
+        let mut rows = self.into_first_result().await?.into_iter();
+
+        let row = match rows.next() {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        if rows.next().is_some() {
+            return Err(Error::UnexpectedRowCount {
+                expected: "zero or one".into(),
+                actual: 2 + rows.count(),
+            });
+        }
+
+        Ok(Some(row))
+    }
+
     /// Convert the stream into a stream of rows, skipping metadata items.
+    /// Rows are yielded lazily as they arrive off the wire, the same as
+    /// polling `QueryStream` itself, so a multi-gigabyte result set can be
+    /// processed in constant memory by consuming this instead of one of the
+    /// eager, [`Vec`]-collecting methods like [`into_first_result`].
+    ///
+    /// [`into_first_result`]: QueryStream::into_first_result
     pub fn into_row_stream(self) -> BoxStream<'a, crate::Result<Row>> {
         let s = self.try_filter_map(|item| async {
             match item {
                 QueryItem::Row(row) => Ok(Some(row)),
-                QueryItem::Metadata(_) => Ok(None),
+                QueryItem::Metadata(_) | QueryItem::Info(_) | QueryItem::ReturnValue(_) => Ok(None),
             }
         });
 
@@ -286,6 +418,13 @@ pub struct ResultMetadata {
 }
 
 impl ResultMetadata {
+    pub(crate) fn new(columns: Arc<Vec<Column>>, result_index: usize) -> Self {
+        Self {
+            columns,
+            result_index,
+        }
+    }
+
     /// Column info. The order is the same as in the following rows.
     pub fn columns(&self) -> &[Column] {
         &self.columns
@@ -306,21 +445,28 @@ pub enum QueryItem {
     Row(Row),
     /// Information of the upcoming row data.
     Metadata(ResultMetadata),
+    /// An informational message sent by the server, e.g. from a `PRINT` or
+    /// `RAISERROR` statement, in the position it arrived relative to the
+    /// surrounding rows.
+    Info(TokenInfo),
+    /// An `OUTPUT` parameter or a user-defined function's return value, in
+    /// the position it arrived relative to the surrounding result sets. A
+    /// stored procedure's `RETURN` status does not produce this item. A
+    /// procedure with several `OUTPUT` parameters sends one of these per
+    /// parameter, in declaration order.
+    ReturnValue(TokenReturnValue),
 }
 
 impl QueryItem {
     pub(crate) fn metadata(columns: Arc<Vec<Column>>, result_index: usize) -> Self {
-        Self::Metadata(ResultMetadata {
-            columns,
-            result_index,
-        })
+        Self::Metadata(ResultMetadata::new(columns, result_index))
     }
 
     /// Returns a reference to the metadata, if the item is of a correct variant.
     pub fn as_metadata(&self) -> Option<&ResultMetadata> {
         match self {
-            QueryItem::Row(_) => None,
             QueryItem::Metadata(ref metadata) => Some(metadata),
+            QueryItem::Row(_) | QueryItem::Info(_) | QueryItem::ReturnValue(_) => None,
         }
     }
 
@@ -328,15 +474,31 @@ impl QueryItem {
     pub fn as_row(&self) -> Option<&Row> {
         match self {
             QueryItem::Row(ref row) => Some(row),
-            QueryItem::Metadata(_) => None,
+            QueryItem::Metadata(_) | QueryItem::Info(_) | QueryItem::ReturnValue(_) => None,
+        }
+    }
+
+    /// Returns a reference to the info message, if the item is of a correct variant.
+    pub fn as_info(&self) -> Option<&TokenInfo> {
+        match self {
+            QueryItem::Info(ref info) => Some(info),
+            QueryItem::Row(_) | QueryItem::Metadata(_) | QueryItem::ReturnValue(_) => None,
+        }
+    }
+
+    /// Returns a reference to the return value, if the item is of a correct variant.
+    pub fn as_return_value(&self) -> Option<&TokenReturnValue> {
+        match self {
+            QueryItem::ReturnValue(ref retval) => Some(retval),
+            QueryItem::Row(_) | QueryItem::Metadata(_) | QueryItem::Info(_) => None,
         }
     }
 
     /// Returns the metadata, if the item is of a correct variant.
     pub fn into_metadata(self) -> Option<ResultMetadata> {
         match self {
-            QueryItem::Row(_) => None,
             QueryItem::Metadata(metadata) => Some(metadata),
+            QueryItem::Row(_) | QueryItem::Info(_) | QueryItem::ReturnValue(_) => None,
         }
     }
 
@@ -344,7 +506,23 @@ impl QueryItem {
     pub fn into_row(self) -> Option<Row> {
         match self {
             QueryItem::Row(row) => Some(row),
-            QueryItem::Metadata(_) => None,
+            QueryItem::Metadata(_) | QueryItem::Info(_) | QueryItem::ReturnValue(_) => None,
+        }
+    }
+
+    /// Returns the info message, if the item is of a correct variant.
+    pub fn into_info(self) -> Option<TokenInfo> {
+        match self {
+            QueryItem::Info(info) => Some(info),
+            QueryItem::Row(_) | QueryItem::Metadata(_) | QueryItem::ReturnValue(_) => None,
+        }
+    }
+
+    /// Returns the return value, if the item is of a correct variant.
+    pub fn into_return_value(self) -> Option<TokenReturnValue> {
+        match self {
+            QueryItem::ReturnValue(retval) => Some(retval),
+            QueryItem::Row(_) | QueryItem::Metadata(_) | QueryItem::Info(_) => None,
         }
     }
 }
@@ -366,9 +544,13 @@ impl<'a> Stream for QueryStream<'a> {
                     let column_meta = meta
                         .columns
                         .iter()
-                        .map(|x| Column {
-                            name: x.col_name.to_string(),
-                            column_type: ColumnType::from(&x.base.ty),
+                        .map(|x| {
+                            Column::new(
+                                x.col_name.as_ref(),
+                                ColumnType::from(&x.base.ty),
+                                x.base.flags,
+                                x.base.ty.collation(),
+                            )
                         })
                         .collect::<Vec<_>>();
 
@@ -386,6 +568,8 @@ impl<'a> Stream for QueryStream<'a> {
                     let columns = this.columns.as_ref().unwrap().clone();
                     let result_index = this.result_set_index.unwrap();
 
+                    this.first_row_at.get_or_insert_with(Instant::now);
+
                     let row = Row {
                         columns,
                         data,
@@ -394,6 +578,10 @@ impl<'a> Stream for QueryStream<'a> {
 
                     Poll::Ready(Some(Ok(QueryItem::Row(row))))
                 }
+                ReceivedToken::Info(info) => Poll::Ready(Some(Ok(QueryItem::Info(info)))),
+                ReceivedToken::ReturnValue(retval) => {
+                    Poll::Ready(Some(Ok(QueryItem::ReturnValue(retval))))
+                }
                 _ => continue,
             };
         }
@@ -0,0 +1,171 @@
+use crate::tds::stream::{ReceivedToken, ResultMetadata};
+use crate::{row::ColumnType, Column, RawRow};
+use futures::{
+    ready,
+    stream::{BoxStream, Peekable},
+    Stream, StreamExt, TryStreamExt,
+};
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    sync::Arc,
+    task::{self, Poll},
+};
+
+/// A stream of [`RawQueryItem`] values, mirroring [`QueryStream`] but
+/// carrying undecoded row bytes instead of a [`Row`], for callers that want
+/// to skip per-column decoding - e.g. to forward rows verbatim, or decode
+/// only a handful of the columns a wide table returns.
+///
+/// Returned by [`Client::raw_query`].
+///
+/// [`QueryStream`]: crate::QueryStream
+/// [`Row`]: crate::Row
+/// [`Client::raw_query`]: crate::Client::raw_query
+pub struct RawQueryStream<'a> {
+    token_stream: Peekable<BoxStream<'a, crate::Result<ReceivedToken>>>,
+    columns: Option<Arc<Vec<Column>>>,
+    result_set_index: Option<usize>,
+}
+
+impl<'a> Debug for RawQueryStream<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawQueryStream")
+            .field(
+                "token_stream",
+                &"BoxStream<'a, crate::Result<ReceivedToken>>",
+            )
+            .finish()
+    }
+}
+
+impl<'a> RawQueryStream<'a> {
+    pub(crate) fn new(token_stream: BoxStream<'a, crate::Result<ReceivedToken>>) -> Self {
+        Self {
+            token_stream: token_stream.peekable(),
+            columns: None,
+            result_set_index: None,
+        }
+    }
+
+    /// Moves the stream forward until having result metadata, stream end or an
+    /// error.
+    pub(crate) async fn forward_to_metadata(&mut self) -> crate::Result<()> {
+        loop {
+            let item = Pin::new(&mut self.token_stream)
+                .peek()
+                .await
+                .map(|r| r.as_ref().map_err(|e| e.clone()))
+                .transpose()?;
+
+            match item {
+                Some(ReceivedToken::NewResultset(_)) => break,
+                Some(_) => {
+                    self.token_stream.try_next().await?;
+                }
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resulting data from a raw query.
+#[derive(Debug)]
+pub enum RawQueryItem {
+    /// A single row's undecoded wire bytes.
+    Row(RawRow),
+    /// Information of the upcoming row data.
+    Metadata(ResultMetadata),
+}
+
+impl RawQueryItem {
+    /// Returns a reference to the metadata, if the item is of a correct variant.
+    pub fn as_metadata(&self) -> Option<&ResultMetadata> {
+        match self {
+            RawQueryItem::Metadata(ref metadata) => Some(metadata),
+            RawQueryItem::Row(_) => None,
+        }
+    }
+
+    /// Returns a reference to the row, if the item is of a correct variant.
+    pub fn as_row(&self) -> Option<&RawRow> {
+        match self {
+            RawQueryItem::Row(ref row) => Some(row),
+            RawQueryItem::Metadata(_) => None,
+        }
+    }
+
+    /// Returns the metadata, if the item is of a correct variant.
+    pub fn into_metadata(self) -> Option<ResultMetadata> {
+        match self {
+            RawQueryItem::Metadata(metadata) => Some(metadata),
+            RawQueryItem::Row(_) => None,
+        }
+    }
+
+    /// Returns the row, if the item is of a correct variant.
+    pub fn into_row(self) -> Option<RawRow> {
+        match self {
+            RawQueryItem::Row(row) => Some(row),
+            RawQueryItem::Metadata(_) => None,
+        }
+    }
+}
+
+impl<'a> Stream for RawQueryStream<'a> {
+    type Item = crate::Result<RawQueryItem>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let token = match ready!(this.token_stream.poll_next_unpin(cx)) {
+                Some(res) => res?,
+                None => return Poll::Ready(None),
+            };
+
+            return match token {
+                ReceivedToken::NewResultset(meta) => {
+                    let column_meta = meta
+                        .columns
+                        .iter()
+                        .map(|x| {
+                            Column::new(
+                                x.col_name.as_ref(),
+                                ColumnType::from(&x.base.ty),
+                                x.base.flags,
+                                x.base.ty.collation(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    let column_meta = Arc::new(column_meta);
+                    this.columns = Some(column_meta.clone());
+
+                    this.result_set_index = this.result_set_index.map(|i| i + 1);
+                    let result_index = *this.result_set_index.get_or_insert(0);
+
+                    let query_item =
+                        RawQueryItem::Metadata(ResultMetadata::new(column_meta, result_index));
+
+                    Poll::Ready(Some(Ok(query_item)))
+                }
+                ReceivedToken::RawRow(data) => {
+                    let columns = this.columns.as_ref().unwrap().clone();
+                    let result_index = this.result_set_index.unwrap();
+
+                    let row = RawRow {
+                        columns,
+                        data,
+                        result_index,
+                    };
+
+                    Poll::Ready(Some(Ok(RawQueryItem::Row(row))))
+                }
+                _ => continue,
+            };
+        }
+    }
+}
@@ -5,16 +5,26 @@ use crate::{
         TokenColMetaData, TokenDone, TokenEnvChange, TokenError, TokenFeatureExtAck, TokenInfo,
         TokenLoginAck, TokenOrder, TokenReturnValue, TokenRow,
     },
-    Error, SqlReadBytes, TokenType,
+    Error, ServerKind, SqlReadBytes, TokenType,
 };
+use bytes::Bytes;
 use futures::{stream::BoxStream, AsyncRead, AsyncWrite, TryStreamExt};
-use std::{convert::TryFrom, sync::Arc};
+use std::{
+    convert::TryFrom,
+    pin::Pin,
+    sync::Arc,
+    task::{self, Poll},
+};
 use tracing::{event, Level};
 
 #[derive(Debug)]
 pub enum ReceivedToken {
     NewResultset(Arc<TokenColMetaData<'static>>),
     Row(TokenRow<'static>),
+    /// A `ROW`/`NBCROW` token's exact wire bytes, produced instead of
+    /// [`ReceivedToken::Row`] when the stream was built with
+    /// [`TokenStream::with_raw_rows`].
+    RawRow(Bytes),
     Done(TokenDone),
     DoneInProc(TokenDone),
     DoneProc(TokenDone),
@@ -28,8 +38,91 @@ pub enum ReceivedToken {
     FeatureExtAck(TokenFeatureExtAck),
 }
 
+/// Mirrors every byte read through a [`SqlReadBytes`] source into an owned
+/// buffer, so [`TokenStream::get_raw_row`]/[`get_raw_nbc_row`] can drive the
+/// normal, already-correct [`TokenRow::decode`]/[`decode_nbc`] to discover a
+/// row's wire length, then keep only the bytes instead of the decoded
+/// values - rather than duplicating every column type's length-reading
+/// logic in a second, decode-free parser.
+///
+/// [`get_raw_row`]: TokenStream::get_raw_row
+/// [`get_raw_nbc_row`]: TokenStream::get_raw_nbc_row
+/// [`decode_nbc`]: TokenRow::decode_nbc
+struct RecordingReader<'a, R> {
+    inner: &'a mut R,
+    recorded: Vec<u8>,
+}
+
+impl<'a, R> RecordingReader<'a, R> {
+    fn new(inner: &'a mut R) -> Self {
+        Self {
+            inner,
+            recorded: Vec::new(),
+        }
+    }
+}
+
+impl<'a, R> AsyncRead for RecordingReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        match Pin::new(&mut *this.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(n)) => {
+                this.recorded.extend_from_slice(&buf[..n]);
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+}
+
+impl<'a, R> SqlReadBytes for RecordingReader<'a, R>
+where
+    R: SqlReadBytes,
+{
+    fn debug_buffer(&self) {
+        self.inner.debug_buffer()
+    }
+
+    fn context(&self) -> &crate::tds::Context {
+        self.inner.context()
+    }
+
+    fn context_mut(&mut self) -> &mut crate::tds::Context {
+        self.inner.context_mut()
+    }
+}
+
+/// How many characters of a `Debug`-formatted token value to keep in a
+/// `TRACE` log line before truncating it. Without this, a single
+/// `NVARCHAR(MAX)` or `VARBINARY(MAX)` value can blow up an otherwise
+/// readable trace into an unusable wall of text.
+const TRACE_VALUE_PREVIEW_LEN: usize = 256;
+
+/// Renders `value`'s `Debug` output for a trace log line, truncating it to
+/// [`TRACE_VALUE_PREVIEW_LEN`] characters and noting the full length when it
+/// doesn't fit.
+fn preview(value: impl std::fmt::Debug) -> String {
+    let full = format!("{:?}", value);
+
+    if full.chars().count() <= TRACE_VALUE_PREVIEW_LEN {
+        full
+    } else {
+        let truncated: String = full.chars().take(TRACE_VALUE_PREVIEW_LEN).collect();
+        format!("{}... ({} chars total)", truncated, full.chars().count())
+    }
+}
+
 pub(crate) struct TokenStream<'a, S: AsyncRead + AsyncWrite + Unpin + Send> {
     conn: &'a mut Connection<S>,
+    raw_rows: bool,
 }
 
 impl<'a, S> TokenStream<'a, S>
@@ -37,7 +130,18 @@ where
     S: AsyncRead + AsyncWrite + Unpin + Send,
 {
     pub(crate) fn new(conn: &'a mut Connection<S>) -> Self {
-        Self { conn }
+        Self {
+            conn,
+            raw_rows: false,
+        }
+    }
+
+    /// Returns [`ReceivedToken::RawRow`] instead of [`ReceivedToken::Row`]
+    /// for `ROW`/`NBCROW` tokens, carrying their exact wire bytes instead of
+    /// decoded values.
+    pub(crate) fn with_raw_rows(mut self) -> Self {
+        self.raw_rows = true;
+        self
     }
 
     pub(crate) async fn flush_done(self) -> crate::Result<TokenDone> {
@@ -84,31 +188,55 @@ where
 
     async fn get_row(&mut self) -> crate::Result<ReceivedToken> {
         let return_value = TokenRow::decode(self.conn).await?;
+        self.conn.record_row();
 
-        event!(Level::TRACE, message = ?return_value);
+        event!(Level::TRACE, "Row: {}", preview(&return_value));
         Ok(ReceivedToken::Row(return_value))
     }
 
     async fn get_nbc_row(&mut self) -> crate::Result<ReceivedToken> {
         let return_value = TokenRow::decode_nbc(self.conn).await?;
+        self.conn.record_row();
 
-        event!(Level::TRACE, message = ?return_value);
+        event!(Level::TRACE, "NbcRow: {}", preview(&return_value));
         Ok(ReceivedToken::Row(return_value))
     }
 
+    async fn get_raw_row(&mut self) -> crate::Result<ReceivedToken> {
+        let mut reader = RecordingReader::new(&mut *self.conn);
+        TokenRow::decode(&mut reader).await?;
+        let bytes = Bytes::from(reader.recorded);
+
+        self.conn.record_row();
+        event!(Level::TRACE, "RawRow: {} bytes", bytes.len());
+        Ok(ReceivedToken::RawRow(bytes))
+    }
+
+    async fn get_raw_nbc_row(&mut self) -> crate::Result<ReceivedToken> {
+        let mut reader = RecordingReader::new(&mut *self.conn);
+        TokenRow::decode_nbc(&mut reader).await?;
+        let bytes = Bytes::from(reader.recorded);
+
+        self.conn.record_row();
+        event!(Level::TRACE, "RawNbcRow: {} bytes", bytes.len());
+        Ok(ReceivedToken::RawRow(bytes))
+    }
+
     async fn get_return_value(&mut self) -> crate::Result<ReceivedToken> {
         let return_value = TokenReturnValue::decode(self.conn).await?;
-        event!(Level::TRACE, message = ?return_value);
+        event!(Level::TRACE, "ReturnValue: {}", preview(&return_value));
         Ok(ReceivedToken::ReturnValue(return_value))
     }
 
     async fn get_return_status(&mut self) -> crate::Result<ReceivedToken> {
         let status = self.conn.read_u32_le().await?;
+        event!(Level::TRACE, "ReturnStatus: {}", status);
         Ok(ReceivedToken::ReturnStatus(status))
     }
 
     async fn get_error(&mut self) -> crate::Result<ReceivedToken> {
         let err = TokenError::decode(self.conn).await?;
+        self.conn.record_error();
         event!(Level::ERROR, message = %err.message, code = err.code);
         Err(Error::Server(err))
     }
@@ -152,6 +280,9 @@ where
             | TokenEnvChange::DefectTransaction => {
                 self.conn.context_mut().set_transaction_descriptor([0; 8]);
             }
+            TokenEnvChange::SqlCollation { ref new, .. } => {
+                self.conn.context_mut().set_collation(*new);
+            }
             _ => (),
         }
 
@@ -163,12 +294,31 @@ where
     async fn get_info(&mut self) -> crate::Result<ReceivedToken> {
         let info = TokenInfo::decode(self.conn).await?;
         event!(Level::INFO, "{}", info.message);
+
+        if self.conn.context().should_escalate_info(info.number) {
+            return Err(crate::Error::Server(TokenError {
+                code: info.number,
+                state: info.state,
+                class: info.class,
+                message: info.message,
+                server: info.server,
+                procedure: info.procedure,
+                line: info.line,
+            }));
+        }
+
         Ok(ReceivedToken::Info(info))
     }
 
     async fn get_login_ack(&mut self) -> crate::Result<ReceivedToken> {
         let ack = TokenLoginAck::decode(self.conn).await?;
         event!(Level::INFO, "{} version {}", ack.prog_name, ack.version);
+
+        let context = self.conn.context();
+        let kind = ServerKind::detect(context.host(), context.instance_name(), &ack.prog_name);
+        self.conn.context_mut().set_server_kind(kind);
+        self.conn.context_mut().set_version(ack.tds_version);
+
         Ok(ReceivedToken::LoginAck(ack))
     }
 
@@ -188,39 +338,132 @@ where
         Ok(ReceivedToken::Sspi(sspi))
     }
 
+    /// Skips a token this driver doesn't decode, using its declared
+    /// `USHORT` length, and logs a warning. Only correct for tokens that
+    /// follow the common variable-length token layout (type byte followed
+    /// by a two-byte length); called only when lenient mode is enabled.
+    async fn skip_unknown_token(&mut self, ty_byte: u8) -> crate::Result<()> {
+        let len = self.conn.read_u16_le().await? as usize;
+
+        event!(
+            Level::WARN,
+            "Skipping unknown token type {:#x} ({} bytes)",
+            ty_byte,
+            len,
+        );
+
+        for _ in 0..len {
+            self.conn.read_u8().await?;
+        }
+
+        Ok(())
+    }
+
     pub fn try_unfold(self) -> BoxStream<'a, crate::Result<ReceivedToken>> {
         let stream = futures::stream::try_unfold(self, |mut this| async move {
-            if this.conn.is_eof() {
-                return Ok(None);
-            }
+            loop {
+                if this.conn.is_eof() {
+                    return Ok(None);
+                }
 
-            let ty_byte = this.conn.read_u8().await?;
-
-            let ty = TokenType::try_from(ty_byte)
-                .map_err(|_| Error::Protocol(format!("invalid token type {:x}", ty_byte).into()))?;
-
-            let token = match ty {
-                TokenType::ReturnStatus => this.get_return_status().await?,
-                TokenType::ColMetaData => this.get_col_metadata().await?,
-                TokenType::Row => this.get_row().await?,
-                TokenType::NbcRow => this.get_nbc_row().await?,
-                TokenType::Done => this.get_done_value().await?,
-                TokenType::DoneProc => this.get_done_proc_value().await?,
-                TokenType::DoneInProc => this.get_done_in_proc_value().await?,
-                TokenType::ReturnValue => this.get_return_value().await?,
-                TokenType::Error => this.get_error().await?,
-                TokenType::Order => this.get_order().await?,
-                TokenType::EnvChange => this.get_env_change().await?,
-                TokenType::Info => this.get_info().await?,
-                TokenType::LoginAck => this.get_login_ack().await?,
-                TokenType::Sspi => this.get_sspi().await?,
-                TokenType::FeatureExtAck => this.get_feature_ext_ack().await?,
-                _ => panic!("Token {:?} unimplemented!", ty),
-            };
-
-            Ok(Some((token, this)))
+                let ty_byte = this.conn.read_u8().await?;
+                let lenient = this.conn.context().lenient_tokens();
+
+                let ty = match TokenType::try_from(ty_byte) {
+                    Ok(ty) => ty,
+                    Err(_) if lenient => {
+                        this.skip_unknown_token(ty_byte).await?;
+                        continue;
+                    }
+                    Err(_) => {
+                        return Err(Error::Protocol(
+                            format!("invalid token type {:x}", ty_byte).into(),
+                        ))
+                    }
+                };
+
+                let token = match ty {
+                    TokenType::ReturnStatus => this.get_return_status().await?,
+                    TokenType::ColMetaData => this.get_col_metadata().await?,
+                    TokenType::Row if this.raw_rows => this.get_raw_row().await?,
+                    TokenType::Row => this.get_row().await?,
+                    TokenType::NbcRow if this.raw_rows => this.get_raw_nbc_row().await?,
+                    TokenType::NbcRow => this.get_nbc_row().await?,
+                    TokenType::Done => this.get_done_value().await?,
+                    TokenType::DoneProc => this.get_done_proc_value().await?,
+                    TokenType::DoneInProc => this.get_done_in_proc_value().await?,
+                    TokenType::ReturnValue => this.get_return_value().await?,
+                    TokenType::Error => this.get_error().await?,
+                    TokenType::Order => this.get_order().await?,
+                    TokenType::EnvChange => this.get_env_change().await?,
+                    TokenType::Info => this.get_info().await?,
+                    TokenType::LoginAck => this.get_login_ack().await?,
+                    TokenType::Sspi => this.get_sspi().await?,
+                    TokenType::FeatureExtAck => this.get_feature_ext_ack().await?,
+                    _ if lenient => {
+                        this.skip_unknown_token(ty_byte).await?;
+                        continue;
+                    }
+                    _ => {
+                        return Err(Error::Protocol(
+                            format!("unimplemented token type {:?}", ty).into(),
+                        ))
+                    }
+                };
+
+                return Ok(Some((token, this)));
+            }
         });
 
         Box::pin(stream)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tds::{
+        codec::{Encode, Packet, PacketHeader, PacketStatus, PacketType},
+        HEADER_BYTES,
+    };
+    use bytes::BytesMut;
+    use futures::io::Cursor;
+
+    /// A single `TabularResult` packet carrying a `ColInfo` token (a real
+    /// token, used in browse mode, that `try_unfold` has no decode arm for)
+    /// followed by a `DONE` token, so a lenient reader has something to
+    /// resume on once it's skipped the `ColInfo`.
+    fn col_info_then_done() -> Vec<u8> {
+        let mut payload = BytesMut::new();
+        payload.extend_from_slice(&[TokenType::ColInfo as u8]);
+        payload.extend_from_slice(&0u16.to_le_bytes());
+        TokenDone::default().encode(&mut payload).unwrap();
+
+        let mut header = PacketHeader::new(payload.len() + HEADER_BYTES, 0);
+        header.set_type(PacketType::TabularResult);
+        header.set_status(PacketStatus::EndOfMessage);
+
+        let mut wire = BytesMut::new();
+        Packet::new(header, payload).encode(&mut wire).unwrap();
+
+        wire.to_vec()
+    }
+
+    #[tokio::test]
+    async fn unhandled_but_valid_token_type_is_a_protocol_error_when_strict() {
+        let mut conn = Connection::for_test(Cursor::new(col_info_then_done()), false);
+        let mut stream = TokenStream::new(&mut conn).try_unfold();
+
+        let err = stream.try_next().await.unwrap_err();
+        assert!(matches!(err, Error::Protocol(_)));
+    }
+
+    #[tokio::test]
+    async fn unhandled_but_valid_token_type_is_skipped_when_lenient() {
+        let mut conn = Connection::for_test(Cursor::new(col_info_then_done()), true);
+        let mut stream = TokenStream::new(&mut conn).try_unfold();
+
+        let token = stream.try_next().await.unwrap();
+        assert!(matches!(token, Some(ReceivedToken::Done(_))));
+    }
+}
@@ -42,7 +42,7 @@ use futures::io::AsyncReadExt;
 ///
 /// It isn't recommended to use this type directly. For dealing with `datetime`,
 /// use the `time` feature of this crate and its `PrimitiveDateTime` type.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DateTime {
     days: i32,
     seconds_fragments: u32,
@@ -98,7 +98,7 @@ impl Encode<BytesMut> for DateTime {
 /// It isn't recommended to use this type directly. For dealing with
 /// `smalldatetime`, use the `time` feature of this crate and its
 /// `PrimitiveDateTime` type.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct SmallDateTime {
     days: u16,
     seconds_fragments: u16,
@@ -151,7 +151,7 @@ impl Encode<BytesMut> for SmallDateTime {
 ///
 /// It isn't recommended to use this type directly. If you want to deal with
 /// `date`, use the `time` feature of this crate and its `Date` type.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg(feature = "tds73")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
 pub struct Date(u32);
@@ -221,6 +221,32 @@ impl PartialEq for Time {
     }
 }
 
+#[cfg(feature = "tds73")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
+impl Eq for Time {}
+
+#[cfg(feature = "tds73")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
+impl std::hash::Hash for Time {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Match the equality above, which treats e.g. a scale-2 and a
+        // scale-3 `Time` with the same underlying value as equal: strip
+        // trailing zero increments until the scale can't be reduced any
+        // further, so two equal values always normalize to the same
+        // (increments, scale) pair.
+        let mut increments = self.increments;
+        let mut scale = self.scale;
+
+        while scale > 0 && increments % 10 == 0 {
+            increments /= 10;
+            scale -= 1;
+        }
+
+        increments.hash(state);
+        scale.hash(state);
+    }
+}
+
 #[cfg(feature = "tds73")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
 impl Time {
@@ -317,7 +343,7 @@ impl Encode<BytesMut> for Time {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg(feature = "tds73")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
 /// A presentation of `datetime2` type in the server.
@@ -379,7 +405,7 @@ impl Encode<BytesMut> for DateTime2 {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg(feature = "tds73")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
 /// A presentation of `datetimeoffset` type in the server.
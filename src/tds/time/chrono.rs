@@ -13,7 +13,7 @@ use crate::tds::codec::ColumnData;
 #[cfg(feature = "tds73")]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
 pub use chrono::offset::{FixedOffset, Utc};
-pub use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+pub use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 #[cfg(feature = "tds73")]
 use std::ops::Sub;
 
@@ -198,6 +198,110 @@ into_sql!(self_,
         });
 );
 
+/// A [`NaiveTime`] paired with an explicit `time(n)` scale (0-7), letting the
+/// caller choose the fractional-second precision used when encoding a
+/// parameter instead of the default, which always writes the maximum scale
+/// of 7 and lets the server truncate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg(feature = "tds73")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tds73")))]
+pub struct NaiveTimeWithScale(NaiveTime, u8);
+
+#[cfg(feature = "tds73")]
+impl NaiveTimeWithScale {
+    /// Pairs a `time` with the wire scale used to encode it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is greater than 7.
+    pub fn new(time: NaiveTime, scale: u8) -> Self {
+        assert!(scale <= 7, "time(n) scale must be between 0 and 7");
+        Self(time, scale)
+    }
+}
+
+/// How an ambiguous, timezone-less `datetime`/`datetime2`/`smalldatetime`
+/// value read from the wire should be interpreted when the caller wants a
+/// timezone-aware result. TDS carries no timezone information for these
+/// types; what a stored value actually means depends entirely on the
+/// application's own convention.
+///
+/// Set with [`Config::datetime_interpretation`] and applied with
+/// [`Client::interpret_datetime`]. [`FromSql`] can't consult it, since the
+/// trait has no way to see a connection's configuration — `row.get::<NaiveDateTime,
+/// _>(i)` stays exactly as ambiguous as the wire data.
+///
+/// [`Config::datetime_interpretation`]: crate::Config::datetime_interpretation
+/// [`Client::interpret_datetime`]: crate::Client::interpret_datetime
+/// [`FromSql`]: crate::FromSql
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeInterpretation {
+    /// Treat the stored value as UTC wall-clock time.
+    Utc,
+    /// Treat the stored value as the local system's wall-clock time.
+    Local,
+    /// Make no assumption about the stored value's timezone.
+    Naive,
+}
+
+impl Default for DateTimeInterpretation {
+    /// Defaults to [`Naive`], preserving the meaning a plain
+    /// [`NaiveDateTime`] already has.
+    ///
+    /// [`Naive`]: Self::Naive
+    fn default() -> Self {
+        Self::Naive
+    }
+}
+
+/// The result of applying a [`DateTimeInterpretation`] to a stored
+/// timezone-less datetime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpretedDateTime {
+    /// The stored value, treated as UTC wall-clock time.
+    Utc(DateTime<Utc>),
+    /// The stored value, treated as the local system's wall-clock time.
+    Local(DateTime<Local>),
+    /// The stored value, unchanged.
+    Naive(NaiveDateTime),
+}
+
+impl DateTimeInterpretation {
+    pub(crate) fn interpret(self, naive: NaiveDateTime) -> InterpretedDateTime {
+        match self {
+            Self::Utc => InterpretedDateTime::Utc(DateTime::from_utc(naive, Utc)),
+            Self::Local => {
+                use chrono::LocalResult;
+
+                let local = match Local.from_local_datetime(&naive) {
+                    LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt,
+                    LocalResult::None => Local.from_utc_datetime(&naive),
+                };
+
+                InterpretedDateTime::Local(local)
+            }
+            Self::Naive => InterpretedDateTime::Naive(naive),
+        }
+    }
+}
+
+#[cfg(feature = "tds73")]
+fn encode_time_with_scale(time: NaiveTime, scale: u8) -> Time {
+    use chrono::Timelike;
+
+    let nanos = time.num_seconds_from_midnight() as u64 * 1e9 as u64 + time.nanosecond() as u64;
+    let full_increments = nanos / 100;
+    let divisor = 10u64.pow((7 - scale) as u32);
+
+    Time::new(full_increments / divisor, scale)
+}
+
+#[cfg(feature = "tds73")]
+to_sql!(self_, NaiveTimeWithScale: (ColumnData::Time, encode_time_with_scale(self_.0, self_.1)););
+
+#[cfg(feature = "tds73")]
+into_sql!(self_, NaiveTimeWithScale: (ColumnData::Time, encode_time_with_scale(self_.0, self_.1)););
+
 #[cfg(not(feature = "tds73"))]
 to_sql!(self_,
         NaiveDateTime: (ColumnData::DateTime, {
@@ -5,7 +5,7 @@ use std::borrow::BorrowMut;
 use std::sync::Arc;
 
 /// Provides information of the location for the schema.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct XmlSchema {
     db_name: String,
     owner: String,
@@ -44,7 +44,7 @@ impl XmlSchema {
 
 /// A representation of XML data in TDS. Holds the data as a UTF-8 string and
 /// and optional information about the schema.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct XmlData {
     data: String,
     schema: Option<Arc<XmlSchema>>,
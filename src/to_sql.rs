@@ -20,7 +20,7 @@ use uuid::Uuid;
 /// |`i64`|`bigint`|
 /// |`f32`|`float(24)`|
 /// |`f64`|`float(53)`|
-/// |`bool`|`bit`|
+/// |`bool`|`bit` (encoded as `Bitn` when the target column type isn't known ahead of time, e.g. for RPC parameters)|
 /// |`String`/`&str` (< 4000 characters)|`nvarchar(4000)`|
 /// |`String`/`&str`|`nvarchar(max)`|
 /// |`Vec<u8>`/`&[u8]` (< 8000 bytes)|`varbinary(8000)`|
@@ -0,0 +1,174 @@
+//! A guard for a SQL Server transaction opened with [`Client::transaction`],
+//! also used for the savepoint-based nested transaction emulation many ORMs
+//! expect: a nested [`Transaction`] "commits" by simply doing nothing
+//! (there's nothing to release - unlike Postgres, T-SQL has no `RELEASE
+//! SAVEPOINT`, a savepoint just stops mattering once nothing rolls back to
+//! it) and "rolls back" with `ROLLBACK TRANSACTION <savepoint>`, undoing
+//! only the work done since the savepoint instead of the whole transaction.
+//!
+//! A [`Transaction`] can only be created from an already-open transaction -
+//! [`Client::transaction`] issues the `BEGIN TRAN` itself, and
+//! [`Transaction::nested`] borrows an existing guard - so there's no
+//! separate "used outside a transaction" error to report; the type system
+//! rules it out.
+//!
+//! Rust has no asynchronous `Drop`, so this guard can't literally commit or
+//! roll back when it goes out of scope the way a synchronous RAII guard
+//! would. Instead, callers must await [`commit`] or [`rollback`]
+//! explicitly; dropping the guard without calling either logs a warning
+//! instead of silently leaving the transaction open, since recovering from
+//! a caller forgetting to finish it isn't something this crate can do on
+//! its own.
+//!
+//! [`Client::transaction`]: crate::Client::transaction
+//! [`commit`]: Transaction::commit
+//! [`rollback`]: Transaction::rollback
+
+use crate::Client;
+use futures::{AsyncRead, AsyncWrite};
+use tracing::{event, Level};
+
+/// A transaction or, when [`nested`] was used to create it, a savepoint
+/// standing in for one - see the [module docs] for how nesting is emulated.
+///
+/// [`nested`]: Transaction::nested
+/// [module docs]: self
+#[must_use = "a transaction is only finished by awaiting `commit` or `rollback`; dropping this guard leaves it open and logs a warning"]
+#[derive(Debug)]
+pub struct Transaction<'a, S: AsyncRead + AsyncWrite + Unpin + Send> {
+    client: Option<&'a mut Client<S>>,
+    // `None` for a real transaction; `Some(name)` for a savepoint standing
+    // in for a nested one.
+    savepoint: Option<String>,
+    depth: u32,
+}
+
+impl<'a, S: AsyncRead + AsyncWrite + Unpin + Send> Transaction<'a, S> {
+    pub(crate) fn new(client: &'a mut Client<S>) -> Self {
+        Self {
+            client: Some(client),
+            savepoint: None,
+            depth: 0,
+        }
+    }
+
+    /// Opens a nested transaction, emulated with `SAVE TRANSACTION` under
+    /// this one, matching the nested-transaction semantics many ORMs
+    /// expect: committing it leaves this transaction's work in place, while
+    /// rolling it back undoes only the work done since the savepoint,
+    /// leaving this transaction open and otherwise untouched.
+    ///
+    /// ```no_run
+    /// # use tiberius::{Config, Client};
+    /// # use tokio_util::compat::TokioAsyncWriteCompatExt;
+    /// # use std::env;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let c_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or(
+    /// #     "server=tcp:localhost,1433;integratedSecurity=true;TrustServerCertificate=true".to_owned(),
+    /// # );
+    /// # let config = Config::from_ado_string(&c_str)?;
+    /// # let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+    /// # tcp.set_nodelay(true)?;
+    /// # let mut client = Client::connect(config, tcp.compat_write()).await?;
+    /// let mut outer = client.transaction().await?;
+    /// outer.execute("INSERT INTO ##Test (id) VALUES (1)", &[]).await?;
+    ///
+    /// let inner = outer.nested().await?;
+    /// // ... changes made here can be undone without losing the insert above ...
+    /// inner.rollback().await?;
+    ///
+    /// outer.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn nested(&mut self) -> crate::Result<Transaction<'_, S>> {
+        let depth = self.depth + 1;
+        let name = format!("tiberius_savepoint_{}", depth);
+
+        self.client()
+            .execute_batch(format!("SAVE TRANSACTION {};", name))
+            .await?;
+
+        Ok(Transaction {
+            client: Some(self.client()),
+            savepoint: Some(name),
+            depth,
+        })
+    }
+
+    /// Whether this guard is still open, i.e. neither [`commit`] nor
+    /// [`rollback`] has consumed it yet. Dropping a guard while this is
+    /// `true` logs the warning described in the [module docs].
+    ///
+    /// [`commit`]: Transaction::commit
+    /// [`rollback`]: Transaction::rollback
+    /// [module docs]: self
+    pub fn is_active(&self) -> bool {
+        self.client.is_some()
+    }
+
+    /// Runs a parameterized statement inside this transaction. See
+    /// [`Client::execute`] for details.
+    pub async fn execute<'b>(
+        &mut self,
+        query: impl Into<std::borrow::Cow<'b, str>>,
+        params: &[&dyn crate::ToSql],
+    ) -> crate::Result<crate::ExecuteResult> {
+        self.client().execute(query, params).await
+    }
+
+    /// Commits the transaction, or, for a nested transaction, does nothing:
+    /// T-SQL has no way to release a savepoint on its own, so the work done
+    /// since it just remains part of the enclosing transaction. See the
+    /// [module docs] for details.
+    ///
+    /// [module docs]: self
+    pub async fn commit(mut self) -> crate::Result<()> {
+        if self.savepoint.is_none() {
+            let client = self.client.take().expect("client taken twice");
+            client.execute_batch("COMMIT TRAN;").await?;
+        } else {
+            self.client.take();
+        }
+
+        Ok(())
+    }
+
+    /// Rolls back the transaction. For a nested transaction, this only
+    /// undoes work done since the savepoint, leaving the enclosing
+    /// transaction open and otherwise untouched; for a top-level
+    /// transaction, it undoes everything done since [`Client::transaction`].
+    ///
+    /// [`Client::transaction`]: crate::Client::transaction
+    pub async fn rollback(mut self) -> crate::Result<()> {
+        let client = self.client.take().expect("client taken twice");
+
+        match &self.savepoint {
+            Some(name) => {
+                client
+                    .execute_batch(format!("ROLLBACK TRANSACTION {};", name))
+                    .await?
+            }
+            None => client.execute_batch("ROLLBACK TRAN;").await?,
+        };
+
+        Ok(())
+    }
+
+    fn client(&mut self) -> &mut Client<S> {
+        self.client.as_mut().expect("client taken twice")
+    }
+}
+
+impl<'a, S: AsyncRead + AsyncWrite + Unpin + Send> Drop for Transaction<'a, S> {
+    fn drop(&mut self) {
+        if self.client.is_some() {
+            event!(
+                Level::WARN,
+                "a Transaction was dropped without calling `commit` or `rollback`; \
+                 the transaction may still be open"
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Compile-time guard for the crate's supported public surface.
+//!
+//! This file doesn't test behavior; it exists so that renaming, hiding or
+//! removing one of these paths breaks the build here instead of turning
+//! into a surprise breaking change for downstream users. It doesn't cover
+//! everything `pub` in the crate - some of that is protocol-level (e.g.
+//! `TokenRow`/`TokenInfo`/`TokenReturnValue`, `ColumnData`) and is exposed
+//! deliberately for advanced uses like bulk-insert and reading `OUTPUT`
+//! parameters, not by accident. What's listed here is the surface a typical
+//! "connect, query, read rows" caller depends on.
+//!
+//! If a change here is intentional, update this file in the same commit as
+//! the API change so the two stay honest about what's actually supported.
+
+#[allow(unused_imports)]
+use tiberius::{
+    error::Error, numeric::Numeric, paginated_query, set_global_defaults, time, xml::XmlData,
+    AuthMethod, CaseSensitive, Client, Collation, Column, ColumnType, Config, ConnectionStats,
+    EncryptionLevel, ExecuteResult, FeatureLevel, FromSql, FromSqlOwned, GlobalConfig,
+    ImpersonationGuard, IntoSql, MultiSubnetFailover, NamedPipe, NegotiatedSettings, ProcResult,
+    Query, QueryItem, QueryStream, RawQueryItem, RawQueryStream, RawRow, Resolver, Result,
+    ResultMetadata, Row, ServerKind, ServiceBrokerMessage, SessionDiagnostics, SetOption,
+    SqlBrowser, TableColumn, TableDescription, TableIndex, ToSql, Transaction, Uuid,
+};
+
+#[allow(unused_imports)]
+use tiberius::prelude::*;
+
+#[test]
+fn public_api_paths_compile() {}
@@ -426,6 +426,39 @@ where
     Ok(())
 }
 
+#[test_on_runtimes]
+async fn exec_returning_output_clause<S>(mut conn: tiberius::Client<S>) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    let table = random_table().await;
+
+    conn.execute(
+        format!(
+            "CREATE TABLE ##{} (id INT IDENTITY, name VARCHAR(50))",
+            table
+        ),
+        &[],
+    )
+    .await?;
+
+    let (result, rows) = conn
+        .exec_returning(
+            format!(
+                "INSERT INTO ##{} (name) OUTPUT inserted.id, inserted.name VALUES (@P1)",
+                table
+            ),
+            &[&"foo"],
+        )
+        .await?;
+
+    assert_eq!(&[1], result.rows_affected());
+    assert_eq!(1, rows.len());
+    assert_eq!(Some("foo"), rows[0].get(1));
+
+    Ok(())
+}
+
 #[test_on_runtimes]
 async fn execute_with_multiple_separate_results<S>(mut conn: tiberius::Client<S>) -> Result<()>
 where
@@ -498,6 +531,9 @@ where
             QueryItem::Row(row) => {
                 assert_eq!(Some("b".repeat(2095).as_str()), row.get(0));
             }
+            QueryItem::Info(_) | QueryItem::ReturnValue(_) => {
+                continue;
+            }
         }
     }
 
@@ -545,6 +581,9 @@ where
             QueryItem::Row(row) => {
                 assert_eq!(Some("b"), row.get(0))
             }
+            QueryItem::Info(_) | QueryItem::ReturnValue(_) => {
+                continue;
+            }
         }
     }
 
@@ -1108,6 +1147,41 @@ where
     Ok(())
 }
 
+#[test_on_runtimes]
+async fn char_and_nchar_trailing_space_padding<S>(mut conn: tiberius::Client<S>) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    let table = random_table().await;
+
+    conn.execute(
+        format!("CREATE TABLE ##{} (c CHAR(10), nc NCHAR(10))", table),
+        &[],
+    )
+    .await?;
+
+    conn.execute(
+        format!("INSERT INTO ##{} (c, nc) VALUES (@P1, @P2)", table),
+        &[&"abc", &"abc"],
+    )
+    .await?;
+
+    let row = conn
+        .query(format!("SELECT c, nc FROM ##{}", table), &[])
+        .await?
+        .into_row()
+        .await?
+        .unwrap();
+
+    assert_eq!(Some("abc       "), row.get::<&str, _>(0));
+    assert_eq!(Some("abc       "), row.get::<&str, _>(1));
+
+    assert_eq!(Some("abc"), row.get_trimmed(0));
+    assert_eq!(Some("abc"), row.get_trimmed(1));
+
+    Ok(())
+}
+
 #[test_on_runtimes]
 async fn text_empty<S>(mut conn: tiberius::Client<S>) -> Result<()>
 where
@@ -1369,6 +1443,45 @@ where
     Ok(())
 }
 
+#[test_on_runtimes]
+async fn varbinary_max_large_payload_via_prepared_statement<S>(
+    mut conn: tiberius::Client<S>,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    let table = random_table().await;
+
+    conn.execute(
+        format!("CREATE TABLE ##{} (content VARBINARY(max))", table),
+        &[],
+    )
+    .await?;
+
+    // Large enough to force the PLP (0xFFFF) unknown-length switch on the
+    // wire instead of the plain two-byte length prefix used below 8000
+    // bytes, like an embedded image or document would.
+    let document: Vec<u8> = (0..100_000).map(|i| (i % 256) as u8).collect();
+
+    let mut query = Query::new(format!("INSERT INTO ##{} (content) VALUES (@P1)", table));
+    query.bind(document.as_slice());
+
+    let inserted = query.execute(&mut conn).await?.total();
+    assert_eq!(1, inserted);
+
+    let row = conn
+        .query(format!("SELECT content FROM ##{}", table), &[])
+        .await?
+        .into_row()
+        .await?
+        .unwrap();
+
+    let result: &[u8] = row.get(0).unwrap();
+    assert_eq!(document, result);
+
+    Ok(())
+}
+
 #[test_on_runtimes]
 async fn numeric_type_u32_presentation<S>(mut conn: tiberius::Client<S>) -> Result<()>
 where